@@ -1,7 +1,10 @@
 use crate::{
     catch::{CatchDifficultyAttributes, CatchPerformanceAttributes},
     mania::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
-    osu::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    osu::{
+        OsuDifficultyAttributes, OsuPerformanceAttributes, OsuVanillaDifficultyAttributes,
+        OsuVanillaPerformanceAttributes,
+    },
     taiko::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
 };
 
@@ -12,6 +15,11 @@ use super::performance::{into::IntoPerformance, Performance};
 pub enum DifficultyAttributes {
     /// osu!standard difficulty calculation result.
     Osu(OsuDifficultyAttributes),
+    /// osu!standard difficulty calculation result of the
+    /// [`ScoringModel::Vanilla`] model.
+    ///
+    /// [`ScoringModel::Vanilla`]: crate::osu::ScoringModel::Vanilla
+    OsuVanilla(OsuVanillaDifficultyAttributes),
     /// osu!taiko difficulty calculation result.
     Taiko(TaikoDifficultyAttributes),
     /// osu!catch difficulty calculation result.
@@ -25,6 +33,7 @@ impl DifficultyAttributes {
     pub const fn stars(&self) -> f64 {
         match self {
             Self::Osu(attrs) => attrs.stars,
+            Self::OsuVanilla(attrs) => attrs.stars,
             Self::Taiko(attrs) => attrs.stars,
             Self::Catch(attrs) => attrs.stars,
             Self::Mania(attrs) => attrs.stars,
@@ -35,6 +44,7 @@ impl DifficultyAttributes {
     pub const fn max_combo(&self) -> u32 {
         match self {
             Self::Osu(attrs) => attrs.max_combo,
+            Self::OsuVanilla(attrs) => attrs.max_combo,
             Self::Taiko(attrs) => attrs.max_combo,
             Self::Catch(attrs) => attrs.max_combo(),
             Self::Mania(attrs) => attrs.max_combo,
@@ -52,6 +62,11 @@ impl DifficultyAttributes {
 pub enum PerformanceAttributes {
     /// osu!standard performance calculation result.
     Osu(OsuPerformanceAttributes),
+    /// osu!standard performance calculation result of the
+    /// [`ScoringModel::Vanilla`] model.
+    ///
+    /// [`ScoringModel::Vanilla`]: crate::osu::ScoringModel::Vanilla
+    OsuVanilla(OsuVanillaPerformanceAttributes),
     /// osu!taiko performance calculation result.
     Taiko(TaikoPerformanceAttributes),
     /// osu!catch performance calculation result.
@@ -65,6 +80,7 @@ impl PerformanceAttributes {
     pub const fn pp(&self) -> f64 {
         match self {
             Self::Osu(attrs) => attrs.pp,
+            Self::OsuVanilla(attrs) => attrs.pp,
             Self::Taiko(attrs) => attrs.pp,
             Self::Catch(attrs) => attrs.pp,
             Self::Mania(attrs) => attrs.pp,
@@ -75,6 +91,7 @@ impl PerformanceAttributes {
     pub const fn stars(&self) -> f64 {
         match self {
             Self::Osu(attrs) => attrs.stars(),
+            Self::OsuVanilla(attrs) => attrs.stars(),
             Self::Taiko(attrs) => attrs.stars(),
             Self::Catch(attrs) => attrs.stars(),
             Self::Mania(attrs) => attrs.stars(),
@@ -85,6 +102,7 @@ impl PerformanceAttributes {
     pub fn difficulty_attributes(&self) -> DifficultyAttributes {
         match self {
             Self::Osu(attrs) => DifficultyAttributes::Osu(attrs.difficulty.clone()),
+            Self::OsuVanilla(attrs) => DifficultyAttributes::OsuVanilla(attrs.difficulty.clone()),
             Self::Taiko(attrs) => DifficultyAttributes::Taiko(attrs.difficulty.clone()),
             Self::Catch(attrs) => DifficultyAttributes::Catch(attrs.difficulty.clone()),
             Self::Mania(attrs) => DifficultyAttributes::Mania(attrs.difficulty.clone()),
@@ -95,6 +113,7 @@ impl PerformanceAttributes {
     pub const fn max_combo(&self) -> u32 {
         match self {
             Self::Osu(attrs) => attrs.difficulty.max_combo,
+            Self::OsuVanilla(attrs) => attrs.difficulty.max_combo,
             Self::Taiko(attrs) => attrs.difficulty.max_combo,
             Self::Catch(attrs) => attrs.difficulty.max_combo(),
             Self::Mania(attrs) => attrs.difficulty.max_combo,
@@ -4,7 +4,10 @@ use crate::{
     any::DifficultyAttributes,
     catch::{Catch, CatchGradualDifficulty},
     mania::{Mania, ManiaGradualDifficulty},
-    model::mode::{ConvertError, IGameMode},
+    model::{
+        mode::{ConvertError, IGameMode},
+        snapshot::{SnapshotError, SnapshotReader},
+    },
     osu::{Osu, OsuGradualDifficulty},
     taiko::{Taiko, TaikoGradualDifficulty},
     Beatmap, Difficulty,
@@ -69,6 +72,92 @@ impl GradualDifficulty {
             GameMode::Mania => Mania::gradual_difficulty(difficulty, map).map(Self::Mania),
         }
     }
+
+    /// Index of the first object that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// The time is adjusted by the clock rate, e.g. with DT an object at
+    /// 3000ms has started after 2000ms of playback.
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual.idx_at_time(time),
+            GradualDifficulty::Taiko(gradual) => gradual.idx_at_time(time),
+            GradualDifficulty::Catch(gradual) => gradual.idx_at_time(time),
+            GradualDifficulty::Mania(gradual) => gradual.idx_at_time(time),
+        }
+    }
+
+    /// Process all remaining objects that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed objects.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual.advance_to_time(time),
+            GradualDifficulty::Taiko(gradual) => gradual.advance_to_time(time),
+            GradualDifficulty::Catch(gradual) => gradual.advance_to_time(time),
+            GradualDifficulty::Mania(gradual) => gradual.advance_to_time(time),
+        }
+    }
+
+    /// Process all remaining objects that have started at the given playback
+    /// time in milliseconds and return the resulting attributes.
+    ///
+    /// Returns `None` if no object has started yet.
+    pub fn attributes_at_time(&mut self, time: f64) -> Option<DifficultyAttributes> {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual
+                .attributes_at_time(time)
+                .map(DifficultyAttributes::Osu),
+            GradualDifficulty::Taiko(gradual) => gradual
+                .attributes_at_time(time)
+                .map(DifficultyAttributes::Taiko),
+            GradualDifficulty::Catch(gradual) => gradual
+                .attributes_at_time(time)
+                .map(DifficultyAttributes::Catch),
+            GradualDifficulty::Mania(gradual) => gradual
+                .attributes_at_time(time)
+                .map(DifficultyAttributes::Mania),
+        }
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// Use [`GradualDifficulty::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        match self {
+            GradualDifficulty::Osu(gradual) => gradual.to_snapshot(),
+            GradualDifficulty::Taiko(gradual) => gradual.to_snapshot(),
+            GradualDifficulty::Catch(gradual) => gradual.to_snapshot(),
+            GradualDifficulty::Mania(gradual) => gradual.to_snapshot(),
+        }
+    }
+
+    /// Restore a snapshot of [`GradualDifficulty::to_snapshot`].
+    ///
+    /// The mode is taken from the snapshot. The [`Difficulty`] and [`Beatmap`]
+    /// must be the same as for the calculator that took the snapshot,
+    /// otherwise [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        match SnapshotReader::mode(snapshot)? {
+            GameMode::Osu => {
+                OsuGradualDifficulty::from_snapshot(difficulty, map, snapshot).map(Self::Osu)
+            }
+            GameMode::Taiko => {
+                TaikoGradualDifficulty::from_snapshot(difficulty, map, snapshot).map(Self::Taiko)
+            }
+            GameMode::Catch => {
+                CatchGradualDifficulty::from_snapshot(difficulty, map, snapshot).map(Self::Catch)
+            }
+            GameMode::Mania => {
+                ManiaGradualDifficulty::from_snapshot(difficulty, map, snapshot).map(Self::Mania)
+            }
+        }
+    }
 }
 
 impl Iterator for GradualDifficulty {
@@ -1,4 +1,8 @@
-use crate::{model::mods::GameMods, Difficulty};
+use crate::{
+    model::mods::GameMods,
+    osu::{PpPlusConfig, ScoringModel},
+    Difficulty,
+};
 
 use super::ModsDependent;
 
@@ -32,6 +36,14 @@ pub struct InspectDifficulty {
     ///
     /// Defaults to `true`.
     pub lazer: Option<bool>,
+    /// Configuration of the PP+ algorithm.
+    ///
+    /// Only relevant for osu!standard.
+    pub pplus_config: Option<PpPlusConfig>,
+    /// Algorithm of osu!standard calculations.
+    ///
+    /// Only relevant for osu!standard.
+    pub scoring_model: Option<ScoringModel>,
 }
 
 impl InspectDifficulty {
@@ -47,6 +59,8 @@ impl InspectDifficulty {
             od,
             hardrock_offsets,
             lazer,
+            pplus_config,
+            scoring_model,
         } = self;
 
         let mut difficulty = Difficulty::new().mods(mods);
@@ -83,6 +97,14 @@ impl InspectDifficulty {
             difficulty = difficulty.lazer(lazer);
         }
 
+        if let Some(pplus_config) = pplus_config {
+            difficulty = difficulty.pplus_config(pplus_config);
+        }
+
+        if let Some(scoring_model) = scoring_model {
+            difficulty = difficulty.scoring_model(scoring_model);
+        }
+
         difficulty
     }
 }
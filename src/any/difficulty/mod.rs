@@ -9,7 +9,7 @@ use crate::{
     catch::Catch,
     mania::Mania,
     model::{beatmap::Beatmap, mode::ConvertError, mods::GameMods},
-    osu::Osu,
+    osu::{patterns::OsuPatterns, Osu, OsuObjectInfo, PpPlusConfig, ScoringModel},
     taiko::Taiko,
     GradualDifficulty, GradualPerformance,
 };
@@ -54,6 +54,8 @@ pub struct Difficulty {
     od: Option<ModsDependent>,
     hardrock_offsets: Option<bool>,
     lazer: Option<bool>,
+    pplus_config: Option<PpPlusConfig>,
+    scoring_model: Option<ScoringModel>,
 }
 
 /// Wrapper for beatmap attributes in [`Difficulty`].
@@ -90,6 +92,8 @@ impl Difficulty {
             od: None,
             hardrock_offsets: None,
             lazer: None,
+            pplus_config: None,
+            scoring_model: None,
         }
     }
 
@@ -106,6 +110,8 @@ impl Difficulty {
             od,
             hardrock_offsets,
             lazer,
+            pplus_config,
+            scoring_model,
         } = self;
 
         InspectDifficulty {
@@ -118,6 +124,8 @@ impl Difficulty {
             od,
             hardrock_offsets,
             lazer,
+            pplus_config,
+            scoring_model,
         }
     }
 
@@ -265,13 +273,44 @@ impl Difficulty {
         self
     }
 
+    /// Specify the configuration of the PP+ algorithm.
+    ///
+    /// Only relevant for osu!standard.
+    ///
+    /// Defaults to [`PpPlusConfig::DEFAULT`].
+    pub const fn pplus_config(mut self, config: PpPlusConfig) -> Self {
+        self.pplus_config = Some(config);
+
+        self
+    }
+
+    /// Specify the algorithm of osu!standard calculations.
+    ///
+    /// Only relevant for osu!standard.
+    ///
+    /// Defaults to [`ScoringModel::PpPlus`].
+    pub const fn scoring_model(mut self, scoring_model: ScoringModel) -> Self {
+        self.scoring_model = Some(scoring_model);
+
+        self
+    }
+
     /// Perform the difficulty calculation.
+    ///
+    /// For osu!standard maps, the [`ScoringModel`] decides whether
+    /// [`DifficultyAttributes::Osu`] or [`DifficultyAttributes::OsuVanilla`]
+    /// are returned.
     #[allow(clippy::missing_panics_doc)]
     pub fn calculate(&self, map: &Beatmap) -> DifficultyAttributes {
         match map.mode {
-            GameMode::Osu => DifficultyAttributes::Osu(
-                Osu::difficulty(self, map).expect("no conversion required"),
-            ),
+            GameMode::Osu => match self.get_scoring_model() {
+                ScoringModel::PpPlus => DifficultyAttributes::Osu(
+                    Osu::difficulty(self, map).expect("no conversion required"),
+                ),
+                ScoringModel::Vanilla => DifficultyAttributes::OsuVanilla(
+                    crate::osu::vanilla_difficulty(self, map).expect("no conversion required"),
+                ),
+            },
             GameMode::Taiko => DifficultyAttributes::Taiko(
                 Taiko::difficulty(self, map).expect("no conversion required"),
             ),
@@ -320,6 +359,22 @@ impl Difficulty {
         M::strains(self, map)
     }
 
+    /// Perform the difficulty calculation on an osu!standard map and return
+    /// the difficulty internals of each hit object.
+    ///
+    /// Returns an error if the map's mode is not osu!standard.
+    pub fn osu_objects(&self, map: &Beatmap) -> Result<Vec<OsuObjectInfo>, ConvertError> {
+        crate::osu::object_infos(self, map)
+    }
+
+    /// Split an osu!standard map into patterns such as streams, bursts, and
+    /// jump chains.
+    ///
+    /// Returns an error if the map's mode is not osu!standard.
+    pub fn osu_patterns(&self, map: &Beatmap) -> Result<OsuPatterns, ConvertError> {
+        crate::osu::patterns::patterns(self, map)
+    }
+
     /// Create a gradual difficulty calculator for a [`Beatmap`].
     pub fn gradual_difficulty(self, map: &Beatmap) -> GradualDifficulty {
         GradualDifficulty::new(self, map)
@@ -383,6 +438,14 @@ impl Difficulty {
     pub(crate) fn get_lazer(&self) -> bool {
         self.lazer.unwrap_or(true)
     }
+
+    pub(crate) fn get_pplus_config(&self) -> PpPlusConfig {
+        self.pplus_config.unwrap_or(PpPlusConfig::DEFAULT)
+    }
+
+    pub(crate) fn get_scoring_model(&self) -> ScoringModel {
+        self.scoring_model.unwrap_or_default()
+    }
 }
 
 const fn non_zero_u64_to_f64(n: NonZeroU64) -> f64 {
@@ -401,6 +464,8 @@ impl Debug for Difficulty {
             od,
             hardrock_offsets,
             lazer,
+            pplus_config,
+            scoring_model,
         } = self;
 
         f.debug_struct("Difficulty")
@@ -413,6 +478,8 @@ impl Debug for Difficulty {
             .field("od", od)
             .field("hardrock_offsets", hardrock_offsets)
             .field("lazer", lazer)
+            .field("pplus_config", pplus_config)
+            .field("scoring_model", scoring_model)
             .finish()
     }
 }
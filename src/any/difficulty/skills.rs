@@ -15,6 +15,9 @@ pub trait StrainSkill: Sized {
 
     fn count_top_weighted_strains(&self, difficulty_value: f64) -> f64;
 
+    /// The strain value of each processed object.
+    fn object_strains(&self) -> &[f64];
+
     fn save_current_peak(&mut self);
 
     fn start_new_section_from<'a>(
@@ -74,10 +77,15 @@ pub fn count_top_weighted_strains(object_strains: &[f64], difficulty_value: f64)
     // * Use a weighted sum of all strains. Constants are arbitrary and give nice values
     object_strains
         .iter()
-        .map(|s| 1.1 / (1.0 + f64::exp(-10.0 * (s / consistent_top_strain - 0.88))))
+        .map(|&s| top_strain_weight(s, consistent_top_strain))
         .sum()
 }
 
+/// How much a single strain counts towards the amount of difficult strains.
+pub fn top_strain_weight(strain: f64, consistent_top_strain: f64) -> f64 {
+    1.1 / (1.0 + f64::exp(-10.0 * (strain / consistent_top_strain - 0.88)))
+}
+
 pub fn difficulty_value(current_strain_peaks: StrainsVec, decay_weight: f64) -> f64 {
     let mut difficulty = 0.0;
     let mut weight = 1.0;
@@ -6,6 +6,7 @@ pub use self::{
     performance::{
         gradual::GradualPerformance,
         into::{IntoModePerformance, IntoPerformance},
+        projection::Projection,
         HitResultPriority, Performance,
     },
     score_state::ScoreState,
@@ -14,6 +15,6 @@ pub use self::{
 
 mod attributes;
 pub(crate) mod difficulty;
-mod performance;
+pub(crate) mod performance;
 mod score_state;
 mod strains;
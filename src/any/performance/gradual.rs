@@ -1,10 +1,13 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::{PerformanceAttributes, ScoreState},
+    any::{PerformanceAttributes, Projection, ScoreState},
     catch::{Catch, CatchGradualPerformance},
     mania::{Mania, ManiaGradualPerformance},
-    model::mode::{ConvertError, IGameMode},
+    model::{
+        mode::{ConvertError, IGameMode},
+        snapshot::{SnapshotError, SnapshotReader},
+    },
     osu::{Osu, OsuGradualPerformance},
     taiko::{Taiko, TaikoGradualPerformance},
     Beatmap, Difficulty,
@@ -153,6 +156,134 @@ impl GradualPerformance {
         }
     }
 
+    /// Index of the first object that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// The time is adjusted by the clock rate, e.g. with DT an object at
+    /// 3000ms has started after 2000ms of playback.
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual.idx_at_time(time),
+            GradualPerformance::Taiko(gradual) => gradual.idx_at_time(time),
+            GradualPerformance::Catch(gradual) => gradual.idx_at_time(time),
+            GradualPerformance::Mania(gradual) => gradual.idx_at_time(time),
+        }
+    }
+
+    /// Process all remaining objects that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed objects.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual.advance_to_time(time),
+            GradualPerformance::Taiko(gradual) => gradual.advance_to_time(time),
+            GradualPerformance::Catch(gradual) => gradual.advance_to_time(time),
+            GradualPerformance::Mania(gradual) => gradual.advance_to_time(time),
+        }
+    }
+
+    /// Process all remaining objects that have started at the given playback
+    /// time in milliseconds and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// Returns `None` if no object has started yet.
+    pub fn attributes_at_time(
+        &mut self,
+        state: ScoreState,
+        time: f64,
+    ) -> Option<PerformanceAttributes> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual
+                .attributes_at_time(state.into(), time)
+                .map(PerformanceAttributes::Osu),
+            GradualPerformance::Taiko(gradual) => gradual
+                .attributes_at_time(state.into(), time)
+                .map(PerformanceAttributes::Taiko),
+            GradualPerformance::Catch(gradual) => gradual
+                .attributes_at_time(state.into(), time)
+                .map(PerformanceAttributes::Catch),
+            GradualPerformance::Mania(gradual) => gradual
+                .attributes_at_time(state.into(), time)
+                .map(PerformanceAttributes::Mania),
+        }
+    }
+
+    /// Project the final score when hitting all remaining objects without
+    /// breaking combo while keeping the current ratio of hitresults.
+    ///
+    /// The `state` must contain the hitresults of all objects that were
+    /// processed so far. The processing state remains unchanged.
+    ///
+    /// Score states don't carry the current combo so for modes with combo
+    /// the current combo is assumed to be `0` after a combo break. The
+    /// projected max combo is then the larger of the state's max combo and
+    /// the combo of all remaining objects. Combo that was rebuilt since the
+    /// last break is not accounted for so the projected max combo may be too
+    /// low in that case.
+    ///
+    /// Returns `None` if the map has no objects.
+    pub fn projection(
+        &mut self,
+        state: ScoreState,
+    ) -> Option<Projection<ScoreState, PerformanceAttributes>> {
+        macro_rules! projection {
+            ( $gradual:ident, $mode:ident ) => {
+                $gradual
+                    .projection(state.into())
+                    .map(|projection| Projection {
+                        state: projection.state.into(),
+                        attributes: PerformanceAttributes::$mode(projection.attributes),
+                    })
+            };
+        }
+
+        match self {
+            GradualPerformance::Osu(gradual) => projection!(gradual, Osu),
+            GradualPerformance::Taiko(gradual) => projection!(gradual, Taiko),
+            GradualPerformance::Catch(gradual) => projection!(gradual, Catch),
+            GradualPerformance::Mania(gradual) => projection!(gradual, Mania),
+        }
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// Use [`GradualPerformance::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        match self {
+            GradualPerformance::Osu(gradual) => gradual.to_snapshot(),
+            GradualPerformance::Taiko(gradual) => gradual.to_snapshot(),
+            GradualPerformance::Catch(gradual) => gradual.to_snapshot(),
+            GradualPerformance::Mania(gradual) => gradual.to_snapshot(),
+        }
+    }
+
+    /// Restore a snapshot of [`GradualPerformance::to_snapshot`].
+    ///
+    /// The mode is taken from the snapshot. The [`Difficulty`] and [`Beatmap`]
+    /// must be the same as for the calculator that took the snapshot,
+    /// otherwise [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        match SnapshotReader::mode(snapshot)? {
+            GameMode::Osu => {
+                OsuGradualPerformance::from_snapshot(difficulty, map, snapshot).map(Self::Osu)
+            }
+            GameMode::Taiko => {
+                TaikoGradualPerformance::from_snapshot(difficulty, map, snapshot).map(Self::Taiko)
+            }
+            GameMode::Catch => {
+                CatchGradualPerformance::from_snapshot(difficulty, map, snapshot).map(Self::Catch)
+            }
+            GameMode::Mania => {
+                ManiaGradualPerformance::from_snapshot(difficulty, map, snapshot).map(Self::Mania)
+            }
+        }
+    }
+
     /// Returns the amount of remaining objects.
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
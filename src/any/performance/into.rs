@@ -3,6 +3,10 @@ use rosu_map::section::general::GameMode;
 use crate::{
     any::{DifficultyAttributes, PerformanceAttributes},
     model::mode::IGameMode,
+    osu::{
+        Osu, OsuPerformance, OsuVanillaDifficultyAttributes, OsuVanillaPerformanceAttributes,
+        ScoringModel,
+    },
     Beatmap, Performance,
 };
 
@@ -95,6 +99,40 @@ impl_from_mode!(
     },
 );
 
+impl<'map> IntoModePerformance<'map, Osu> for OsuVanillaDifficultyAttributes {
+    fn into_performance(self) -> OsuPerformance<'map> {
+        let mut performance = OsuPerformance::from_map_or_attrs(self.shared().into());
+        performance.difficulty = performance.difficulty.scoring_model(ScoringModel::Vanilla);
+        performance.vanilla_attrs = Some(Box::new(self));
+
+        performance
+    }
+}
+
+impl<'map> IntoModePerformance<'map, Osu> for OsuVanillaPerformanceAttributes {
+    fn into_performance(self) -> OsuPerformance<'map> {
+        <OsuVanillaDifficultyAttributes as IntoModePerformance<'map, Osu>>::into_performance(
+            self.difficulty,
+        )
+    }
+}
+
+impl<'a> IntoPerformance<'a> for OsuVanillaDifficultyAttributes {
+    fn into_performance(self) -> Performance<'a> {
+        Performance::Osu(<Self as IntoModePerformance<'a, Osu>>::into_performance(
+            self,
+        ))
+    }
+}
+
+impl<'a> IntoPerformance<'a> for OsuVanillaPerformanceAttributes {
+    fn into_performance(self) -> Performance<'a> {
+        Performance::Osu(<Self as IntoModePerformance<'a, Osu>>::into_performance(
+            self,
+        ))
+    }
+}
+
 impl<'a> IntoPerformance<'a> for Beatmap {
     fn into_performance(self) -> Performance<'a> {
         match self.mode {
@@ -121,6 +159,7 @@ impl<'a> IntoPerformance<'a> for DifficultyAttributes {
     fn into_performance(self) -> Performance<'a> {
         match self {
             Self::Osu(attrs) => Performance::Osu(attrs.into()),
+            Self::OsuVanilla(attrs) => Performance::Osu(attrs.into()),
             Self::Taiko(attrs) => Performance::Taiko(attrs.into()),
             Self::Catch(attrs) => Performance::Catch(attrs.into()),
             Self::Mania(attrs) => Performance::Mania(attrs.into()),
@@ -132,6 +171,7 @@ impl<'a> IntoPerformance<'a> for PerformanceAttributes {
     fn into_performance(self) -> Performance<'a> {
         match self {
             Self::Osu(attrs) => Performance::Osu(attrs.difficulty.into()),
+            Self::OsuVanilla(attrs) => Performance::Osu(attrs.difficulty.into()),
             Self::Taiko(attrs) => Performance::Taiko(attrs.difficulty.into()),
             Self::Catch(attrs) => Performance::Catch(attrs.difficulty.into()),
             Self::Mania(attrs) => Performance::Mania(attrs.difficulty.into()),
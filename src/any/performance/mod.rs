@@ -1,7 +1,11 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    catch::CatchPerformance, mania::ManiaPerformance, osu::OsuPerformance, taiko::TaikoPerformance,
+    catch::CatchPerformance,
+    mania::ManiaPerformance,
+    model::mode::ConvertError,
+    osu::{OsuPerformance, ScoringModel},
+    taiko::TaikoPerformance,
     Difficulty, GameMods,
 };
 
@@ -11,6 +15,8 @@ use super::{attributes::PerformanceAttributes, score_state::ScoreState};
 
 pub mod gradual;
 pub mod into;
+pub mod projection;
+pub mod solver;
 
 /// Performance calculator on maps of any mode.
 #[derive(Clone, Debug, PartialEq)]
@@ -49,12 +55,21 @@ impl<'map> Performance<'map> {
 
     /// Consume the performance calculator and calculate
     /// performance attributes for the given parameters.
+    ///
+    /// osu!standard calculators that were created from attributes always use
+    /// the [`ScoringModel`] of those attributes, regardless of the specified
+    /// model.
     #[allow(clippy::missing_panics_doc)]
     pub fn calculate(self) -> PerformanceAttributes {
         match self {
-            Self::Osu(o) => {
-                PerformanceAttributes::Osu(o.calculate().expect("no conversion required"))
-            }
+            Self::Osu(o) => match o.available_scoring_model() {
+                ScoringModel::PpPlus => {
+                    PerformanceAttributes::Osu(o.calculate().expect("no conversion required"))
+                }
+                ScoringModel::Vanilla => PerformanceAttributes::OsuVanilla(
+                    o.calculate_vanilla().expect("no conversion required"),
+                ),
+            },
             Self::Taiko(t) => {
                 PerformanceAttributes::Taiko(t.calculate().expect("no conversion required"))
             }
@@ -67,6 +82,94 @@ impl<'map> Performance<'map> {
         }
     }
 
+    /// Calculate the performance attributes for each of the given accuracies
+    /// between `0.0` and `100.0`.
+    ///
+    /// All other parameters such as mods, combo, or misses are kept fixed
+    /// while the remaining hitresults are generated from the accuracy.
+    /// Difficulty attributes are only calculated once and shared across all
+    /// accuracies.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn accuracy_curve(self, accuracies: &[f64]) -> Vec<PerformanceAttributes> {
+        match self {
+            Self::Osu(o) => match o.available_scoring_model() {
+                ScoringModel::PpPlus => {
+                    curve(o.accuracy_curve(accuracies), PerformanceAttributes::Osu)
+                }
+                ScoringModel::Vanilla => curve(
+                    o.accuracy_curve_vanilla(accuracies),
+                    PerformanceAttributes::OsuVanilla,
+                ),
+            },
+            Self::Taiko(t) => curve(t.accuracy_curve(accuracies), PerformanceAttributes::Taiko),
+            Self::Catch(f) => curve(f.accuracy_curve(accuracies), PerformanceAttributes::Catch),
+            Self::Mania(m) => curve(m.accuracy_curve(accuracies), PerformanceAttributes::Mania),
+        }
+    }
+
+    /// Calculate the performance attributes for each of the given amounts of
+    /// misses.
+    ///
+    /// All other parameters such as mods, combo, or accuracy are kept fixed.
+    /// Difficulty attributes are only calculated once and shared across all
+    /// amounts of misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn miss_curve(self, misses: &[u32]) -> Vec<PerformanceAttributes> {
+        match self {
+            Self::Osu(o) => match o.available_scoring_model() {
+                ScoringModel::PpPlus => curve(o.miss_curve(misses), PerformanceAttributes::Osu),
+                ScoringModel::Vanilla => curve(
+                    o.miss_curve_vanilla(misses),
+                    PerformanceAttributes::OsuVanilla,
+                ),
+            },
+            Self::Taiko(t) => curve(t.miss_curve(misses), PerformanceAttributes::Taiko),
+            Self::Catch(f) => curve(f.miss_curve(misses), PerformanceAttributes::Catch),
+            Self::Mania(m) => curve(m.miss_curve(misses), PerformanceAttributes::Mania),
+        }
+    }
+
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach `target_pp`.
+    ///
+    /// All other parameters such as mods, combo, or misses are kept fixed
+    /// while the remaining hitresults are generated from the accuracy.
+    ///
+    /// Returns `None` if `target_pp` cannot be reached even with 100%
+    /// accuracy.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn required_accuracy(self, target_pp: f64) -> Option<f64> {
+        match self {
+            Self::Osu(o) => match o.available_scoring_model() {
+                ScoringModel::PpPlus => o.required_accuracy(target_pp),
+                ScoringModel::Vanilla => o.required_accuracy_vanilla(target_pp),
+            },
+            Self::Taiko(t) => t.required_accuracy(target_pp),
+            Self::Catch(f) => f.required_accuracy(target_pp),
+            Self::Mania(m) => m.required_accuracy(target_pp),
+        }
+        .expect("no conversion required")
+    }
+
+    /// Find the maximal amount of misses that still reaches `target_pp`.
+    ///
+    /// All other parameters such as mods, combo, or accuracy are kept fixed.
+    ///
+    /// Returns `None` if `target_pp` cannot be reached even without misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn allowed_misses(self, target_pp: f64) -> Option<u32> {
+        match self {
+            Self::Osu(o) => match o.available_scoring_model() {
+                ScoringModel::PpPlus => o.allowed_misses(target_pp),
+                ScoringModel::Vanilla => o.allowed_misses_vanilla(target_pp),
+            },
+            Self::Taiko(t) => t.allowed_misses(target_pp),
+            Self::Catch(f) => f.allowed_misses(target_pp),
+            Self::Mania(m) => m.allowed_misses(target_pp),
+        }
+        .expect("no conversion required")
+    }
+
     /// Attempt to convert the map to the specified mode.
     ///
     /// Returns `Err(self)` if the conversion is incompatible or no beatmap is
@@ -253,6 +356,19 @@ impl<'map> Performance<'map> {
         }
     }
 
+    /// Specify which [`ScoringModel`] is used to calculate osu!standard
+    /// attributes.
+    ///
+    /// Only relevant for osu!standard. Honored by [`Performance::calculate`]
+    /// as well as the curves and solvers of [`Performance`].
+    pub fn scoring_model(self, scoring_model: ScoringModel) -> Self {
+        if let Self::Osu(o) = self {
+            Self::Osu(o.scoring_model(scoring_model))
+        } else {
+            self
+        }
+    }
+
     /// Provide parameters through a [`ScoreState`].
     pub fn state(self, state: ScoreState) -> Self {
         match self {
@@ -432,6 +548,17 @@ impl<'map> Performance<'map> {
     }
 }
 
+fn curve<A>(
+    curve: Result<Vec<A>, ConvertError>,
+    f: fn(A) -> PerformanceAttributes,
+) -> Vec<PerformanceAttributes> {
+    curve
+        .expect("no conversion required")
+        .into_iter()
+        .map(f)
+        .collect()
+}
+
 /// While generating remaining hitresults, decide how they should be distributed.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[non_exhaustive]
@@ -516,4 +643,120 @@ mod tests {
         let _ = DifficultyAttributes::Osu(OsuDifficultyAttributes::default()).performance();
         let _ = PerformanceAttributes::Taiko(TaikoPerformanceAttributes::default()).performance();
     }
+
+    #[test]
+    fn curves() {
+        for id in [2785319, 1028484, 2118524, 1638954] {
+            let map = Beatmap::from_path(format!("./resources/{id}.osu")).unwrap();
+            let calc = || Performance::new(&map).mods(8); // HD
+
+            let accuracies = [95.0, 97.5, 100.0];
+            let acc_curve = calc().misses(1).accuracy_curve(&accuracies);
+
+            for (acc, attrs) in accuracies.into_iter().zip(acc_curve.iter()) {
+                assert_eq!(attrs, &calc().misses(1).accuracy(acc).calculate());
+            }
+
+            assert!(acc_curve.windows(2).all(|w| w[0].pp() <= w[1].pp()));
+
+            let misses = [0, 2, 5];
+            let miss_curve = calc().n100(5).miss_curve(&misses);
+
+            for (n_misses, attrs) in misses.into_iter().zip(miss_curve.iter()) {
+                assert_eq!(attrs, &calc().n100(5).misses(n_misses).calculate());
+            }
+
+            assert!(miss_curve.windows(2).all(|w| w[0].pp() >= w[1].pp()));
+        }
+    }
+
+    #[test]
+    fn solver() {
+        for id in [2785319, 1028484, 2118524, 1638954] {
+            let map = Beatmap::from_path(format!("./resources/{id}.osu")).unwrap();
+            let attrs = Difficulty::new().calculate(&map);
+            let calc = || Performance::new(attrs.clone());
+
+            let max_pp = calc().misses(1).calculate().pp();
+            let target_pp = max_pp * 0.9;
+
+            let acc = calc().misses(1).required_accuracy(target_pp).unwrap();
+            assert!(calc().misses(1).accuracy(acc).calculate().pp() >= target_pp);
+            assert!(calc().misses(1).accuracy(acc - 1.0).calculate().pp() < target_pp);
+            assert_eq!(calc().misses(1).required_accuracy(max_pp + 1.0), None);
+
+            let misses = calc().allowed_misses(target_pp).unwrap();
+            assert!(calc().misses(misses).calculate().pp() >= target_pp);
+            assert!(calc().misses(misses + 1).calculate().pp() < target_pp);
+            assert_eq!(calc().allowed_misses(f64::INFINITY), None);
+        }
+    }
+
+    #[test]
+    fn scoring_model() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new()
+            .mods(8)
+            .scoring_model(ScoringModel::Vanilla);
+
+        let DifficultyAttributes::OsuVanilla(attrs) = difficulty.calculate(&map) else {
+            panic!("expected vanilla difficulty attributes");
+        };
+
+        let PerformanceAttributes::OsuVanilla(from_map) = Performance::new(&map)
+            .difficulty(difficulty.clone())
+            .misses(2)
+            .calculate()
+        else {
+            panic!("expected vanilla performance attributes");
+        };
+
+        let PerformanceAttributes::OsuVanilla(from_attrs) = Performance::new(attrs.clone())
+            .mods(8)
+            .misses(2)
+            .calculate()
+        else {
+            panic!("expected vanilla performance attributes");
+        };
+
+        assert_eq!(from_map, from_attrs);
+        assert_eq!(from_map.difficulty, attrs);
+
+        let pplus = Performance::new(&map).mods(8).misses(2).calculate();
+        assert!(matches!(pplus, PerformanceAttributes::Osu(_)));
+
+        // PP+ attributes can't be calculated with the vanilla model
+        let pplus_attrs = Difficulty::new().mods(8).calculate(&map);
+        let fallback = Performance::new(pplus_attrs)
+            .mods(8)
+            .scoring_model(ScoringModel::Vanilla)
+            .misses(2)
+            .calculate();
+        assert_eq!(fallback, pplus);
+
+        // Curves and solvers use the same model
+        let calc = || Performance::new(attrs.clone()).mods(8).misses(2);
+
+        let accuracies = [95.0, 100.0];
+        let acc_curve = calc().accuracy_curve(&accuracies);
+
+        for (acc, attrs) in accuracies.into_iter().zip(acc_curve.iter()) {
+            assert!(matches!(attrs, PerformanceAttributes::OsuVanilla(_)));
+            assert_eq!(attrs, &calc().accuracy(acc).calculate());
+        }
+
+        let miss_curve = Performance::new(&map)
+            .difficulty(difficulty)
+            .miss_curve(&[2]);
+        assert_eq!(miss_curve, [PerformanceAttributes::OsuVanilla(from_map)]);
+
+        let target_pp = from_attrs.pp * 0.9;
+        let acc = calc().required_accuracy(target_pp).unwrap();
+        assert!(calc().accuracy(acc).calculate().pp() >= target_pp);
+        assert!(calc().accuracy(acc - 1.0).calculate().pp() < target_pp);
+
+        let misses = calc().allowed_misses(target_pp).unwrap();
+        assert!(calc().misses(misses).calculate().pp() >= target_pp);
+        assert!(calc().misses(misses + 1).calculate().pp() < target_pp);
+    }
 }
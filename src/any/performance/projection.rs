@@ -0,0 +1,75 @@
+use std::cmp;
+
+/// The projected final score of a gradual performance calculation.
+///
+/// The projection assumes that all remaining objects are hit without breaking
+/// combo while keeping the current ratio of hitresults. After a combo break,
+/// the current combo is assumed to be `0`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Projection<S, A> {
+    /// The projected score state at the end of the map.
+    pub state: S,
+    /// The performance attributes of the projected score state.
+    pub attributes: A,
+}
+
+/// Distribute `remaining` hits proportionally to the current `counts`.
+///
+/// Hits that are lost due to rounding as well as all hits for empty `counts`
+/// go to the first i.e. best hitresult.
+pub(crate) fn distribute<const N: usize>(remaining: u32, counts: [u32; N]) -> [u32; N] {
+    let total: u64 = counts.iter().copied().map(u64::from).sum();
+    let mut distributed = [0; N];
+
+    for (dst, count) in distributed.iter_mut().zip(counts).skip(1) {
+        *dst = (u64::from(remaining) * u64::from(count))
+            .checked_div(total)
+            .map_or(0, |n| n as u32);
+    }
+
+    let rest: u32 = distributed.iter().skip(1).sum();
+
+    if let Some(first) = distributed.first_mut() {
+        *first = remaining - rest;
+    }
+
+    distributed
+}
+
+/// The maximum combo after hitting all remaining objects without breaking
+/// combo.
+///
+/// `curr_max_combo` and `final_max_combo` are the map's maximum combo up to
+/// the current object and for the whole map.
+///
+/// After a combo break the current combo is assumed to be `0` because score
+/// states don't carry it, so combo that was rebuilt since the break is
+/// ignored.
+pub(crate) fn full_combo(max_combo: u32, curr_max_combo: u32, final_max_combo: u32) -> u32 {
+    if max_combo >= curr_max_combo {
+        // No combo break so far
+        final_max_combo
+    } else {
+        cmp::max(max_combo, final_max_combo - curr_max_combo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distribute_remaining() {
+        assert_eq!(distribute(10, [0, 0, 0]), [10, 0, 0]);
+        assert_eq!(distribute(10, [8, 2, 0]), [8, 2, 0]);
+        assert_eq!(distribute(10, [1, 1, 1]), [4, 3, 3]);
+        assert_eq!(distribute(0, [5, 5]), [0, 0]);
+    }
+
+    #[test]
+    fn full_combo_remaining() {
+        assert_eq!(full_combo(100, 100, 500), 500);
+        assert_eq!(full_combo(60, 100, 500), 400);
+        assert_eq!(full_combo(60, 490, 500), 60);
+    }
+}
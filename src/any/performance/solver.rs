@@ -0,0 +1,82 @@
+/// Accuracy difference in percent at which the bisection stops.
+const ACCURACY_PRECISION: f64 = 0.001;
+
+/// Bisect the minimal accuracy between `0.0` and `100.0` for which `pp`
+/// reaches `target_pp`.
+///
+/// Assumes that `pp` does not decrease for increasing accuracy.
+pub(crate) fn min_accuracy(target_pp: f64, mut pp: impl FnMut(f64) -> f64) -> Option<f64> {
+    if pp(100.0) < target_pp {
+        return None;
+    } else if pp(0.0) >= target_pp {
+        return Some(0.0);
+    }
+
+    let mut low = 0.0;
+    let mut high = 100.0;
+
+    while high - low > ACCURACY_PRECISION {
+        let mid = (low + high) / 2.0;
+
+        if pp(mid) >= target_pp {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Some(high)
+}
+
+/// Bisect the maximal amount of misses up to `max_misses` for which `pp`
+/// still reaches `target_pp`.
+///
+/// Assumes that `pp` does not increase for increasing misses.
+pub(crate) fn max_misses(
+    target_pp: f64,
+    max_misses: u32,
+    mut pp: impl FnMut(u32) -> f64,
+) -> Option<u32> {
+    if pp(0) < target_pp {
+        return None;
+    }
+
+    let mut low = 0;
+    let mut high = max_misses;
+
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+
+        if pp(mid) >= target_pp {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Some(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bisect_accuracy() {
+        let pp = |acc: f64| acc * 5.0;
+
+        assert!((min_accuracy(250.0, pp).unwrap() - 50.0).abs() < ACCURACY_PRECISION);
+        assert_eq!(min_accuracy(0.0, pp), Some(0.0));
+        assert_eq!(min_accuracy(501.0, pp), None);
+    }
+
+    #[test]
+    fn bisect_misses() {
+        let pp = |misses: u32| 100.0 - f64::from(misses) * 10.0;
+
+        assert_eq!(max_misses(75.0, 20, pp), Some(2));
+        assert_eq!(max_misses(70.0, 20, pp), Some(3));
+        assert_eq!(max_misses(-500.0, 20, pp), Some(20));
+        assert_eq!(max_misses(101.0, 20, pp), None);
+    }
+}
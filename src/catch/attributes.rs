@@ -21,6 +21,15 @@ pub struct CatchDifficultyAttributes {
     pub is_convert: bool,
 }
 
+snapshot_state!(CatchDifficultyAttributes {
+    stars,
+    ar,
+    n_fruits,
+    n_droplets,
+    n_tiny_droplets,
+    is_convert,
+});
+
 impl CatchDifficultyAttributes {
     /// Return the maximum combo.
     pub const fn max_combo(&self) -> u32 {
@@ -10,7 +10,11 @@ use crate::{
         convert::convert_objects,
         CatchDifficultyAttributes,
     },
-    model::mode::ConvertError,
+    model::{
+        mode::ConvertError,
+        snapshot::{self, SnapshotError, SnapshotReader, SnapshotState, SnapshotWriter},
+    },
+    util::object_times::ObjectTimes,
     Beatmap, Difficulty,
 };
 
@@ -61,13 +65,17 @@ pub struct CatchGradualDifficulty {
     attrs: CatchDifficultyAttributes,
     /// The delta of object counts after each palpable object
     count: Vec<GradualObjectCount>,
+    times: ObjectTimes,
     diff_objects: Box<[CatchDifficultyObject]>,
     movement: Movement,
+    checksum: u64,
+    final_attrs: Option<CatchDifficultyAttributes>,
 }
 
 impl CatchGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!catch maps.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let checksum = snapshot::checksum(map, &difficulty);
         let map = map.convert_ref(GameMode::Catch, difficulty.get_mods())?;
 
         let clock_rate = difficulty.get_clock_rate();
@@ -97,6 +105,7 @@ impl CatchGradualDifficulty {
         );
 
         let count = count.into_gradual();
+        let times = ObjectTimes::new(palpable_objects.iter().map(|h| h.start_time), clock_rate);
         let movement = Movement::new(half_catcher_width, clock_rate);
 
         Ok(Self {
@@ -104,10 +113,130 @@ impl CatchGradualDifficulty {
             difficulty,
             attrs,
             count,
+            times,
             diff_objects,
             movement,
+            checksum,
+            final_attrs: None,
         })
     }
+
+    /// Index of the first fruit or droplet that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// The time is adjusted by the clock rate, e.g. with DT a fruit or droplet at
+    /// 3000ms has started after 2000ms of playback.
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.times.passed_count(time)
+    }
+
+    /// Process all remaining fruits and droplets that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed fruits and droplets.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        let n = self.idx_at_time(time).saturating_sub(self.idx);
+
+        if n > 0 {
+            self.nth(n - 1);
+        }
+
+        n
+    }
+
+    /// Process all remaining fruits and droplets that have started at the given playback
+    /// time in milliseconds and return the resulting attributes.
+    ///
+    /// Returns `None` if no fruit or droplet has started yet.
+    pub fn attributes_at_time(&mut self, time: f64) -> Option<CatchDifficultyAttributes> {
+        match self.idx_at_time(time).saturating_sub(self.idx) {
+            0 => self.current(),
+            n => self.nth(n - 1),
+        }
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// The snapshot contains the amount of processed fruits and droplets, the
+    /// movement strains, and the attributes so far.
+    ///
+    /// Use [`CatchGradualDifficulty::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        snapshot::to_snapshot(self, GameMode::Catch, self.checksum)
+    }
+
+    /// Restore a snapshot of [`CatchGradualDifficulty::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let mut gradual = Self::new(difficulty, map)?;
+        let checksum = gradual.checksum;
+        snapshot::from_snapshot(&mut gradual, GameMode::Catch, checksum, snapshot)?;
+
+        Ok(gradual)
+    }
+
+    /// The attributes after processing all fruits and droplets.
+    ///
+    /// The processing state remains unchanged and the result is cached.
+    pub(crate) fn final_attributes(&mut self) -> Option<CatchDifficultyAttributes> {
+        if self.final_attrs.is_none() {
+            let idx = self.idx;
+            let attrs = self.attrs.clone();
+            let movement = self.movement.clone();
+
+            self.final_attrs = match self.len() {
+                0 => self.current(),
+                _ => self.nth(usize::MAX),
+            };
+
+            self.idx = idx;
+            self.attrs = attrs;
+            self.movement = movement;
+        }
+
+        self.final_attrs.clone()
+    }
+
+    /// The attributes of all fruits and droplets that have been processed so
+    /// far.
+    pub(crate) fn current(&self) -> Option<CatchDifficultyAttributes> {
+        if self.idx == 0 {
+            return None;
+        }
+
+        let mut attrs = self.attrs.clone();
+
+        let movement = self.movement.cloned_difficulty_value();
+        DifficultyValues::eval(&mut attrs, movement);
+
+        Some(attrs)
+    }
+}
+
+impl SnapshotState for CatchGradualDifficulty {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.idx.write(writer);
+        self.attrs.write(writer);
+        self.movement.write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        self.idx.read(reader)?;
+
+        if self.idx > self.count.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+
+        self.attrs.read(reader)?;
+        self.movement.read(reader)
+    }
 }
 
 impl Iterator for CatchGradualDifficulty {
@@ -128,12 +257,7 @@ impl Iterator for CatchGradualDifficulty {
         self.attrs.add_object_count(self.count[self.idx]);
         self.idx += 1;
 
-        let mut attrs = self.attrs.clone();
-
-        let movement = self.movement.cloned_difficulty_value();
-        DifficultyValues::eval(&mut attrs, movement);
-
-        Some(attrs)
+        self.current()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -221,4 +345,57 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn time() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = CatchGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        // The map starts with a banana shower so the first fruit starts at
+        // 2616ms i.e. at 1744ms with DT
+        assert_eq!(gradual.idx_at_time(1743.0), 0);
+        assert_eq!(gradual.idx_at_time(1744.0), 1);
+        assert!(gradual.attributes_at_time(1743.0).is_none());
+
+        for time in [20_000.0, 20_000.0, 50_000.0, f64::MAX] {
+            let idx = gradual.idx_at_time(time);
+            let attrs = gradual.attributes_at_time(time).unwrap();
+
+            let expected = difficulty
+                .clone()
+                .passed_objects(idx as u32)
+                .calculate_for_mode::<Catch>(&map)
+                .unwrap();
+
+            assert_eq!(gradual.idx, idx);
+            assert_eq!(attrs, expected);
+        }
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.advance_to_time(f64::MAX), 0);
+    }
+
+    #[test]
+    fn snapshot() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(16); // HR
+        let mut gradual = CatchGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+        gradual.nth(299);
+
+        let snapshot = gradual.to_snapshot();
+        let restored =
+            CatchGradualDifficulty::from_snapshot(difficulty.clone(), &map, &snapshot).unwrap();
+
+        assert_eq!(restored.idx, gradual.idx);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(restored.last(), gradual.last());
+
+        assert!(matches!(
+            CatchGradualDifficulty::from_snapshot(Difficulty::new(), &map, &snapshot),
+            Err(SnapshotError::ChecksumMismatch)
+        ));
+    }
 }
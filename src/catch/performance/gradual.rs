@@ -1,6 +1,10 @@
 use crate::{
-    catch::{CatchGradualDifficulty, CatchPerformanceAttributes, CatchScoreState},
-    model::mode::ConvertError,
+    any::{performance::projection, Projection},
+    catch::{
+        CatchDifficultyAttributes, CatchGradualDifficulty, CatchPerformanceAttributes,
+        CatchScoreState,
+    },
+    model::{mode::ConvertError, snapshot::SnapshotError},
     Beatmap, Difficulty,
 };
 
@@ -115,19 +119,41 @@ impl CatchGradualPerformance {
     ///
     /// Note that the count is zero-indexed, so `n=0` will process 1 object,
     /// `n=1` will process 2, and so on.
-    #[allow(clippy::missing_panics_doc)]
     pub fn nth(&mut self, state: CatchScoreState, n: usize) -> Option<CatchPerformanceAttributes> {
-        let performance = self
-            .difficulty
-            .nth(n)?
-            .performance()
-            .state(state)
-            .difficulty(self.difficulty.difficulty.clone())
-            .passed_objects(self.difficulty.idx as u32)
-            .calculate()
-            .expect("no conversion required");
+        let attrs = self.difficulty.nth(n)?;
 
-        Some(performance)
+        Some(self.calculate(attrs, state, self.difficulty.idx))
+    }
+
+    /// Index of the first fruit or droplet that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// See [`CatchGradualDifficulty::idx_at_time`].
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.difficulty.idx_at_time(time)
+    }
+
+    /// Process all remaining fruits and droplets that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed fruits and droplets.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        self.difficulty.advance_to_time(time)
+    }
+
+    /// Process all remaining fruits and droplets that have started at the given playback
+    /// time in milliseconds and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// Returns `None` if no fruit or droplet has started yet.
+    pub fn attributes_at_time(
+        &mut self,
+        state: CatchScoreState,
+        time: f64,
+    ) -> Option<CatchPerformanceAttributes> {
+        let attrs = self.difficulty.attributes_at_time(time)?;
+
+        Some(self.calculate(attrs, state, self.difficulty.idx))
     }
 
     /// Returns the amount of remaining objects.
@@ -135,6 +161,91 @@ impl CatchGradualPerformance {
     pub fn len(&self) -> usize {
         self.difficulty.len()
     }
+
+    /// Project the final score when catching all remaining fruits and droplets
+    /// while keeping the current ratio of caught tiny droplets.
+    ///
+    /// The `state` must contain the hitresults of all fruits and droplets
+    /// that were processed so far. The processing state remains unchanged.
+    ///
+    /// Score states don't carry the current combo so after a combo break the
+    /// current combo is assumed to be `0`. The projected max combo is then
+    /// the larger of the state's max combo and the combo of all remaining
+    /// fruits and droplets. Combo that was rebuilt since the last break is not accounted
+    /// for so the projected max combo may be too low in that case.
+    ///
+    /// Returns `None` if the map has no fruits or droplets.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn projection(
+        &mut self,
+        state: CatchScoreState,
+    ) -> Option<Projection<CatchScoreState, CatchPerformanceAttributes>> {
+        let final_attrs = self.difficulty.final_attributes()?;
+        let curr_attrs = self.difficulty.current().unwrap_or_default();
+
+        let [tiny_droplets, tiny_droplet_misses] = projection::distribute(
+            final_attrs.n_tiny_droplets - curr_attrs.n_tiny_droplets,
+            [state.tiny_droplets, state.tiny_droplet_misses],
+        );
+
+        let projected = CatchScoreState {
+            max_combo: projection::full_combo(
+                state.max_combo,
+                curr_attrs.max_combo(),
+                final_attrs.max_combo(),
+            ),
+            fruits: state.fruits + final_attrs.n_fruits - curr_attrs.n_fruits,
+            droplets: state.droplets + final_attrs.n_droplets - curr_attrs.n_droplets,
+            tiny_droplets: state.tiny_droplets + tiny_droplets,
+            tiny_droplet_misses: state.tiny_droplet_misses + tiny_droplet_misses,
+            misses: state.misses,
+        };
+
+        let n_objects = self.difficulty.idx + self.difficulty.len();
+        let attributes = self.calculate(final_attrs, projected.clone(), n_objects);
+
+        Some(Projection {
+            state: projected,
+            attributes,
+        })
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// See [`CatchGradualDifficulty::to_snapshot`].
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        self.difficulty.to_snapshot()
+    }
+
+    /// Restore a snapshot of [`CatchGradualPerformance::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let difficulty = CatchGradualDifficulty::from_snapshot(difficulty, map, snapshot)?;
+
+        Ok(Self { difficulty })
+    }
+
+    fn calculate(
+        &self,
+        attrs: CatchDifficultyAttributes,
+        state: CatchScoreState,
+        passed_objects: usize,
+    ) -> CatchPerformanceAttributes {
+        attrs
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(passed_objects as u32)
+            .calculate()
+            .expect("no conversion required")
+    }
 }
 
 #[cfg(test)]
@@ -185,4 +296,32 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn projection() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(88); // HDHRDT
+        let mut gradual = CatchGradualPerformance::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_2nd = CatchGradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        let state = CatchPerformance::new(&map)
+            .difficulty(difficulty.clone())
+            .passed_objects(100)
+            .generate_state()
+            .unwrap();
+
+        gradual.nth(state.clone(), 99).unwrap();
+        let projection = gradual.projection(state.clone()).unwrap();
+
+        let mut regular_calc = CatchPerformance::new(&map).difficulty(difficulty);
+        assert_eq!(projection.state, regular_calc.generate_state().unwrap());
+        assert_eq!(projection.attributes, regular_calc.calculate().unwrap());
+
+        // The processing state is unaffected
+        assert_eq!(
+            gradual.next(state.clone()),
+            gradual_2nd.nth(state.clone(), 100)
+        );
+    }
 }
@@ -5,7 +5,7 @@ use rosu_map::section::general::GameMode;
 use self::calculator::CatchPerformanceCalculator;
 
 use crate::{
-    any::{Difficulty, IntoModePerformance, IntoPerformance},
+    any::{performance::solver, Difficulty, IntoModePerformance, IntoPerformance},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
     util::map_or_attrs::MapOrAttrs,
@@ -429,6 +429,118 @@ impl<'map> CatchPerformance<'map> {
         Ok(CatchPerformanceCalculator::new(attrs, self.difficulty.get_mods(), state).calculate())
     }
 
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach `target_pp`.
+    ///
+    /// All other parameters such as mods, combo, or misses are kept fixed
+    /// while fruits, droplets, and tiny droplets are generated from the accuracy.
+    /// Difficulty attributes are only calculated once.
+    ///
+    /// Returns `None` if `target_pp` cannot be reached even with 100%
+    /// accuracy.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn required_accuracy(mut self, target_pp: f64) -> Result<Option<f64>, ConvertError> {
+        self.prepare_accuracy()?;
+
+        Ok(solver::min_accuracy(target_pp, |acc| {
+            let calc = self.clone().accuracy(acc);
+
+            calc.calculate().expect("no conversion required").pp
+        }))
+    }
+
+    /// Find the maximal amount of misses that still reaches `target_pp`.
+    ///
+    /// All other parameters such as mods, combo, or accuracy are kept fixed.
+    /// Difficulty attributes are only calculated once.
+    ///
+    /// Returns `None` if `target_pp` cannot be reached even without misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn allowed_misses(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        self.cache_attributes()?;
+        let max_misses = self.clone().generate_state()?.total_hits();
+
+        Ok(solver::max_misses(target_pp, max_misses, |misses| {
+            let calc = self.clone().misses(misses);
+
+            calc.calculate().expect("no conversion required").pp
+        }))
+    }
+
+    /// Calculate the performance attributes for each of the given accuracies
+    /// between `0.0` and `100.0`.
+    ///
+    /// All other parameters such as mods, combo, or misses are kept fixed
+    /// while fruits, droplets, and tiny droplets are generated from the accuracy.
+    /// Difficulty attributes are only calculated once and shared across all
+    /// accuracies.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn accuracy_curve(
+        mut self,
+        accuracies: &[f64],
+    ) -> Result<Vec<CatchPerformanceAttributes>, ConvertError> {
+        self.prepare_accuracy()?;
+
+        let curve = accuracies
+            .iter()
+            .map(|&acc| {
+                let calc = self.clone().accuracy(acc);
+
+                calc.calculate().expect("no conversion required")
+            })
+            .collect();
+
+        Ok(curve)
+    }
+
+    /// Calculate the performance attributes for each of the given amounts of
+    /// misses.
+    ///
+    /// All other parameters such as mods, combo, or accuracy are kept fixed.
+    /// Difficulty attributes are only calculated once and shared across all
+    /// amounts of misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn miss_curve(
+        mut self,
+        misses: &[u32],
+    ) -> Result<Vec<CatchPerformanceAttributes>, ConvertError> {
+        self.cache_attributes()?;
+
+        let curve = misses
+            .iter()
+            .map(|&n_misses| {
+                let calc = self.clone().misses(n_misses);
+
+                calc.calculate().expect("no conversion required")
+            })
+            .collect();
+
+        Ok(curve)
+    }
+
+    /// Cache the difficulty attributes and reset all hitresults that are
+    /// generated from the accuracy.
+    fn prepare_accuracy(&mut self) -> Result<(), ConvertError> {
+        self.cache_attributes()?;
+        self.fruits = None;
+        self.droplets = None;
+        self.tiny_droplets = None;
+        self.tiny_droplet_misses = None;
+
+        Ok(())
+    }
+
+    /// Calculate the difficulty attributes so that repeated calculations on
+    /// clones don't need to.
+    fn cache_attributes(&mut self) -> Result<(), ConvertError> {
+        if let MapOrAttrs::Map(ref map) = self.map_or_attrs {
+            let attrs = self.difficulty.calculate_for_mode::<Catch>(map)?;
+            self.map_or_attrs.insert_attrs(attrs);
+        }
+
+        Ok(())
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Catch>) -> Self {
         Self {
             map_or_attrs,
@@ -476,6 +588,10 @@ impl<'map> TryFrom<OsuPerformance<'map>> for CatchPerformance<'map> {
             n100,
             n50,
             misses,
+            hit_error: _,
+            miss_indices: _,
+            positional_misses: _,
+            vanilla_attrs: _,
             hitresult_priority: _,
         } = osu;
 
@@ -5,7 +5,12 @@ use rosu_map::section::general::GameMode;
 use crate::{
     any::difficulty::skills::StrainSkill,
     mania::{convert, object::ObjectParams},
-    model::{hit_object::HitObject, mode::ConvertError},
+    model::{
+        hit_object::HitObject,
+        mode::ConvertError,
+        snapshot::{self, SnapshotError, SnapshotReader, SnapshotState, SnapshotWriter},
+    },
+    util::object_times::ObjectTimes,
     Beatmap, Difficulty,
 };
 
@@ -50,21 +55,30 @@ pub struct ManiaGradualDifficulty {
     pub(crate) idx: usize,
     pub(crate) difficulty: Difficulty,
     objects_is_circle: Box<[bool]>,
+    times: ObjectTimes,
     is_convert: bool,
     strain: Strain,
     diff_objects: Box<[ManiaDifficultyObject]>,
     note_state: NoteState,
+    checksum: u64,
+    final_attrs: Option<ManiaDifficultyAttributes>,
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct NoteState {
     curr_combo: u32,
     n_hold_notes: u32,
 }
 
+snapshot_state!(NoteState {
+    curr_combo,
+    n_hold_notes,
+});
+
 impl ManiaGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!mania maps.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let checksum = snapshot::checksum(map, &difficulty);
         let mut map = map.convert_ref(GameMode::Mania, difficulty.get_mods())?;
 
         if difficulty.get_mods().ho() {
@@ -99,6 +113,8 @@ impl ManiaGradualDifficulty {
         let objects_is_circle: Box<[_]> =
             map.hit_objects.iter().map(HitObject::is_circle).collect();
 
+        let times = ObjectTimes::new(map.hit_objects.iter().map(|h| h.start_time), clock_rate);
+
         if let Some(h) = map.hit_objects.first() {
             let hit_object = ManiaObject::new(h, total_columns, &mut params);
 
@@ -114,12 +130,132 @@ impl ManiaGradualDifficulty {
             idx: 0,
             difficulty,
             objects_is_circle,
+            times,
             is_convert: map.is_convert,
             strain,
             diff_objects,
             note_state,
+            checksum,
+            final_attrs: None,
         })
     }
+
+    /// Index of the first hit object that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// The time is adjusted by the clock rate, e.g. with DT a hit object at
+    /// 3000ms has started after 2000ms of playback.
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.times.passed_count(time)
+    }
+
+    /// Process all remaining hit objects that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed hit objects.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        let n = self.idx_at_time(time).saturating_sub(self.idx);
+
+        if n > 0 {
+            self.nth(n - 1);
+        }
+
+        n
+    }
+
+    /// Process all remaining hit objects that have started at the given playback
+    /// time in milliseconds and return the resulting attributes.
+    ///
+    /// Returns `None` if no hit object has started yet.
+    pub fn attributes_at_time(&mut self, time: f64) -> Option<ManiaDifficultyAttributes> {
+        match self.idx_at_time(time).saturating_sub(self.idx) {
+            0 => self.current(),
+            n => self.nth(n - 1),
+        }
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// The snapshot contains the amount of processed hit objects, the strains,
+    /// and the combo so far.
+    ///
+    /// Use [`ManiaGradualDifficulty::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        snapshot::to_snapshot(self, GameMode::Mania, self.checksum)
+    }
+
+    /// Restore a snapshot of [`ManiaGradualDifficulty::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let mut gradual = Self::new(difficulty, map)?;
+        let checksum = gradual.checksum;
+        snapshot::from_snapshot(&mut gradual, GameMode::Mania, checksum, snapshot)?;
+
+        Ok(gradual)
+    }
+
+    /// The attributes after processing all hit objects.
+    ///
+    /// The processing state remains unchanged and the result is cached.
+    pub(crate) fn final_attributes(&mut self) -> Option<ManiaDifficultyAttributes> {
+        if self.final_attrs.is_none() {
+            let idx = self.idx;
+            let strain = self.strain.clone();
+            let note_state = self.note_state.clone();
+
+            self.final_attrs = match self.len() {
+                0 => self.current(),
+                _ => self.nth(usize::MAX),
+            };
+
+            self.idx = idx;
+            self.strain = strain;
+            self.note_state = note_state;
+        }
+
+        self.final_attrs.clone()
+    }
+
+    /// The attributes of all hit objects that have been processed so far.
+    pub(crate) fn current(&self) -> Option<ManiaDifficultyAttributes> {
+        if self.idx == 0 {
+            return None;
+        }
+
+        Some(ManiaDifficultyAttributes {
+            stars: self.strain.cloned_difficulty_value() * DIFFICULTY_MULTIPLIER,
+            max_combo: self.note_state.curr_combo,
+            n_objects: self.idx as u32,
+            n_hold_notes: self.note_state.n_hold_notes,
+            is_convert: self.is_convert,
+        })
+    }
+}
+
+impl SnapshotState for ManiaGradualDifficulty {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.idx.write(writer);
+        self.strain.write(writer);
+        self.note_state.write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        self.idx.read(reader)?;
+
+        if self.idx > self.objects_is_circle.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+
+        self.strain.read(reader)?;
+        self.note_state.read(reader)
+    }
 }
 
 impl Iterator for ManiaGradualDifficulty {
@@ -147,13 +283,7 @@ impl Iterator for ManiaGradualDifficulty {
 
         self.idx += 1;
 
-        Some(ManiaDifficultyAttributes {
-            stars: self.strain.cloned_difficulty_value() * DIFFICULTY_MULTIPLIER,
-            max_combo: self.note_state.curr_combo,
-            n_objects: self.idx as u32,
-            n_hold_notes: self.note_state.n_hold_notes,
-            is_convert: self.is_convert,
-        })
+        self.current()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -270,4 +400,56 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn time() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = ManiaGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        // The first three hit objects start at 23ms i.e. at 15.3ms with DT
+        assert_eq!(gradual.idx_at_time(15.0), 0);
+        assert_eq!(gradual.idx_at_time(16.0), 3);
+        assert!(gradual.attributes_at_time(15.0).is_none());
+
+        for time in [20_000.0, 20_000.0, 50_000.0, f64::MAX] {
+            let idx = gradual.idx_at_time(time);
+            let attrs = gradual.attributes_at_time(time).unwrap();
+
+            let expected = difficulty
+                .clone()
+                .passed_objects(idx as u32)
+                .calculate_for_mode::<Mania>(&map)
+                .unwrap();
+
+            assert_eq!(gradual.idx, idx);
+            assert_eq!(attrs, expected);
+        }
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.advance_to_time(f64::MAX), 0);
+    }
+
+    #[test]
+    fn snapshot() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = ManiaGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+        gradual.nth(299);
+
+        let snapshot = gradual.to_snapshot();
+        let restored =
+            ManiaGradualDifficulty::from_snapshot(difficulty.clone(), &map, &snapshot).unwrap();
+
+        assert_eq!(restored.idx, gradual.idx);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(restored.last(), gradual.last());
+
+        assert!(matches!(
+            ManiaGradualDifficulty::from_snapshot(Difficulty::new(), &map, &snapshot),
+            Err(SnapshotError::ChecksumMismatch)
+        ));
+    }
 }
@@ -5,6 +5,7 @@ use crate::{
 };
 
 define_skill! {
+    #[derive(Clone)]
     #[allow(clippy::struct_field_names)]
     pub struct Strain: StrainDecaySkill => [ManiaDifficultyObject][ManiaDifficultyObject] {
         start_times: Box<[f64]>,
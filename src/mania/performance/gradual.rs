@@ -1,4 +1,9 @@
-use crate::{mania::ManiaGradualDifficulty, model::mode::ConvertError, Beatmap, Difficulty};
+use crate::{
+    any::{performance::projection, Projection},
+    mania::{ManiaDifficultyAttributes, ManiaGradualDifficulty},
+    model::{mode::ConvertError, snapshot::SnapshotError},
+    Beatmap, Difficulty,
+};
 
 use super::{ManiaPerformanceAttributes, ManiaScoreState};
 
@@ -93,19 +98,41 @@ impl ManiaGradualPerformance {
     ///
     /// Note that the count is zero-indexed, so `n=0` will process 1 object,
     /// `n=1` will process 2, and so on.
-    #[allow(clippy::missing_panics_doc)]
     pub fn nth(&mut self, state: ManiaScoreState, n: usize) -> Option<ManiaPerformanceAttributes> {
-        let performance = self
-            .difficulty
-            .nth(n)?
-            .performance()
-            .state(state)
-            .difficulty(self.difficulty.difficulty.clone())
-            .passed_objects(self.difficulty.idx as u32)
-            .calculate()
-            .expect("no conversion required");
+        let attrs = self.difficulty.nth(n)?;
+
+        Some(self.calculate(attrs, state, self.difficulty.idx))
+    }
+
+    /// Index of the first hit object that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// See [`ManiaGradualDifficulty::idx_at_time`].
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.difficulty.idx_at_time(time)
+    }
+
+    /// Process all remaining hit objects that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed hit objects.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        self.difficulty.advance_to_time(time)
+    }
+
+    /// Process all remaining hit objects that have started at the given playback
+    /// time in milliseconds and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// Returns `None` if no hit object has started yet.
+    pub fn attributes_at_time(
+        &mut self,
+        state: ManiaScoreState,
+        time: f64,
+    ) -> Option<ManiaPerformanceAttributes> {
+        let attrs = self.difficulty.attributes_at_time(time)?;
 
-        Some(performance)
+        Some(self.calculate(attrs, state, self.difficulty.idx))
     }
 
     /// Returns the amount of remaining objects.
@@ -113,6 +140,91 @@ impl ManiaGradualPerformance {
     pub fn len(&self) -> usize {
         self.difficulty.len()
     }
+
+    /// Project the final score when hitting all remaining hit objects while
+    /// keeping the current ratio of hitresults.
+    ///
+    /// The `state` must contain the hitresults of all hit objects that were
+    /// processed so far. The processing state remains unchanged.
+    ///
+    /// Returns `None` if the map has no hit objects.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn projection(
+        &mut self,
+        state: ManiaScoreState,
+    ) -> Option<Projection<ManiaScoreState, ManiaPerformanceAttributes>> {
+        let final_attrs = self.difficulty.final_attributes()?;
+        let curr_attrs = self.difficulty.current().unwrap_or_default();
+
+        let difficulty = &self.difficulty.difficulty;
+        let classic = !difficulty.get_lazer() || difficulty.get_mods().cl();
+
+        // Hold notes have an additional judgement without classic scoring
+        let mut remaining = final_attrs.n_objects - curr_attrs.n_objects;
+
+        if !classic {
+            remaining += final_attrs.n_hold_notes - curr_attrs.n_hold_notes;
+        }
+
+        let [n320, n300, n200, n100, n50] = projection::distribute(
+            remaining,
+            [state.n320, state.n300, state.n200, state.n100, state.n50],
+        );
+
+        let projected = ManiaScoreState {
+            n320: state.n320 + n320,
+            n300: state.n300 + n300,
+            n200: state.n200 + n200,
+            n100: state.n100 + n100,
+            n50: state.n50 + n50,
+            misses: state.misses,
+        };
+
+        let n_objects = final_attrs.n_objects as usize;
+        let attributes = self.calculate(final_attrs, projected.clone(), n_objects);
+
+        Some(Projection {
+            state: projected,
+            attributes,
+        })
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// See [`ManiaGradualDifficulty::to_snapshot`].
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        self.difficulty.to_snapshot()
+    }
+
+    /// Restore a snapshot of [`ManiaGradualPerformance::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let difficulty = ManiaGradualDifficulty::from_snapshot(difficulty, map, snapshot)?;
+
+        Ok(Self { difficulty })
+    }
+
+    fn calculate(
+        &self,
+        attrs: ManiaDifficultyAttributes,
+        state: ManiaScoreState,
+        passed_objects: usize,
+    ) -> ManiaPerformanceAttributes {
+        attrs
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(passed_objects as u32)
+            .calculate()
+            .expect("no conversion required")
+    }
 }
 
 #[cfg(test)]
@@ -175,4 +287,32 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn projection() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(88); // HDHRDT
+        let mut gradual = ManiaGradualPerformance::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_2nd = ManiaGradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        let state = ManiaPerformance::new(&map)
+            .difficulty(difficulty.clone())
+            .passed_objects(100)
+            .generate_state()
+            .unwrap();
+
+        gradual.nth(state.clone(), 99).unwrap();
+        let projection = gradual.projection(state.clone()).unwrap();
+
+        let mut regular_calc = ManiaPerformance::new(&map).difficulty(difficulty);
+        assert_eq!(projection.state, regular_calc.generate_state().unwrap());
+        assert_eq!(projection.attributes, regular_calc.calculate().unwrap());
+
+        // The processing state is unaffected
+        assert_eq!(
+            gradual.next(state.clone()),
+            gradual_2nd.nth(state.clone(), 100)
+        );
+    }
 }
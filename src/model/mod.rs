@@ -12,3 +12,9 @@ pub mod mode;
 
 /// Gamemods related types.
 pub mod mods;
+
+/// Replay related types.
+pub mod replay;
+
+/// Snapshot related types.
+pub mod snapshot;
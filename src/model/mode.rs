@@ -5,7 +5,7 @@ use std::{
 
 pub use rosu_map::section::general::GameMode;
 
-use crate::Difficulty;
+use crate::{osu::ScoringModel, Difficulty};
 
 use super::beatmap::Beatmap;
 
@@ -62,13 +62,16 @@ pub trait IGameMode: Sized {
 }
 
 /// Error type when failing to convert a [`Beatmap`] from one [`GameMode`] to
-/// another.
+/// another or when lacking the attributes of a [`ScoringModel`].
 #[derive(Copy, Clone, Debug)]
 pub enum ConvertError {
     /// Cannot convert an already converted map
     AlreadyConverted,
     /// Cannot convert from [`GameMode`] `from` to `to`
     Convert { from: GameMode, to: GameMode },
+    /// Cannot calculate the [`ScoringModel`] `model` because neither a map
+    /// nor attributes of that model were given
+    MissingAttributes { model: ScoringModel },
 }
 
 impl Error for ConvertError {
@@ -86,6 +89,9 @@ impl Display for ConvertError {
             ConvertError::Convert { from, to } => {
                 write!(f, "Cannot convert from {from:?} to {to:?}")
             }
+            ConvertError::MissingAttributes { model } => {
+                write!(f, "Cannot calculate the {model:?} model without a map or its attributes")
+            }
         }
     }
 }
@@ -79,6 +79,17 @@ impl GameMods {
         }
     }
 
+    /// Returns the legacy bitflags of the mods.
+    ///
+    /// Mods without a legacy representation are not included.
+    pub(crate) fn bits(&self) -> u32 {
+        match self {
+            Self::Lazer(ref mods) => mods.bits(),
+            Self::Intermode(ref mods) => mods.bits(),
+            Self::Legacy(mods) => mods.bits(),
+        }
+    }
+
     pub(crate) fn od_ar_hp_multiplier(&self) -> f64 {
         if self.hr() {
             1.4
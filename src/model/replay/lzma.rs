@@ -0,0 +1,628 @@
+//! Decoder for the `.lzma` ("LZMA alone") format used for replay frames.
+//!
+//! Follows the reference decoder of the LZMA specification.
+
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Error when decoding LZMA compressed data.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LzmaError {
+    /// The header's properties are out of range.
+    InvalidProperties,
+    /// The compressed data ended prematurely.
+    UnexpectedEof,
+    /// The compressed data is corrupted.
+    Corrupted,
+    /// The decompressed data exceeds the maximum size.
+    TooLarge,
+}
+
+impl std::error::Error for LzmaError {}
+
+impl Display for LzmaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let s = match self {
+            Self::InvalidProperties => "invalid LZMA properties",
+            Self::UnexpectedEof => "unexpected end of LZMA data",
+            Self::Corrupted => "corrupted LZMA data",
+            Self::TooLarge => "decompressed LZMA data is too large",
+        };
+
+        f.write_str(s)
+    }
+}
+
+const HEADER_LEN: usize = 13;
+
+const NUM_BIT_MODEL_TOTAL_BITS: u32 = 11;
+const BIT_MODEL_TOTAL: u16 = 1 << NUM_BIT_MODEL_TOTAL_BITS;
+const NUM_MOVE_BITS: u32 = 5;
+const PROB_INIT: u16 = BIT_MODEL_TOTAL / 2;
+const TOP_VALUE: u32 = 1 << 24;
+
+const NUM_STATES: usize = 12;
+const NUM_POS_BITS_MAX: usize = 4;
+const NUM_LEN_TO_POS_STATES: usize = 4;
+const NUM_ALIGN_BITS: u32 = 4;
+const START_POS_MODEL_INDEX: u32 = 4;
+const END_POS_MODEL_INDEX: u32 = 14;
+const NUM_FULL_DISTANCES: usize = 1 << (END_POS_MODEL_INDEX >> 1);
+const MATCH_MIN_LEN: usize = 2;
+
+/// Upper bound of the compression ratio that is trusted when reserving
+/// memory for the output so that a bogus header size can't abort.
+const MAX_RESERVE_RATIO: usize = 32;
+
+/// Maximum size of the decompressed data.
+///
+/// Replay frames of even hour-long maps take only a few megabytes so this
+/// only prevents small streams of long matches from exhausting memory.
+const MAX_OUTPUT_LEN: usize = 1 << 28;
+
+/// Decompress `.lzma` data, i.e. a 13 byte header followed by the LZMA
+/// stream.
+pub fn decompress(input: &[u8]) -> Result<Vec<u8>, LzmaError> {
+    decompress_with_limit(input, MAX_OUTPUT_LEN)
+}
+
+/// Same as [`decompress`] but fails with [`LzmaError::TooLarge`] as soon as
+/// the output would exceed `limit` bytes.
+fn decompress_with_limit(input: &[u8], limit: usize) -> Result<Vec<u8>, LzmaError> {
+    if input.len() < HEADER_LEN {
+        return Err(LzmaError::UnexpectedEof);
+    }
+
+    let mut props = u32::from(input[0]);
+
+    if props >= 9 * 5 * 5 {
+        return Err(LzmaError::InvalidProperties);
+    }
+
+    let lc = props % 9;
+    props /= 9;
+    let lp = props % 5;
+    let pb = props / 5;
+
+    let mut size_bytes = [0; 8];
+    size_bytes.copy_from_slice(&input[5..HEADER_LEN]);
+    let unpack_size = match u64::from_le_bytes(size_bytes) {
+        u64::MAX => None,
+        size => Some(usize::try_from(size).map_err(|_| LzmaError::TooLarge)?),
+    };
+
+    if unpack_size.is_some_and(|size| size > limit) {
+        return Err(LzmaError::TooLarge);
+    }
+
+    let rc = RangeDecoder::new(&input[HEADER_LEN..])?;
+
+    Decoder::new(rc, lc, lp, pb, limit).decode(unpack_size)
+}
+
+struct RangeDecoder<'a> {
+    input: &'a [u8],
+    range: u32,
+    code: u32,
+}
+
+impl<'a> RangeDecoder<'a> {
+    fn new(input: &'a [u8]) -> Result<Self, LzmaError> {
+        let mut this = Self {
+            input,
+            range: u32::MAX,
+            code: 0,
+        };
+
+        if this.next_byte()? != 0 {
+            return Err(LzmaError::Corrupted);
+        }
+
+        for _ in 0..4 {
+            this.code = (this.code << 8) | u32::from(this.next_byte()?);
+        }
+
+        if this.code == this.range {
+            return Err(LzmaError::Corrupted);
+        }
+
+        Ok(this)
+    }
+
+    fn next_byte(&mut self) -> Result<u8, LzmaError> {
+        let (&byte, rest) = self.input.split_first().ok_or(LzmaError::UnexpectedEof)?;
+        self.input = rest;
+
+        Ok(byte)
+    }
+
+    const fn is_finished_ok(&self) -> bool {
+        self.code == 0
+    }
+
+    fn normalize(&mut self) -> Result<(), LzmaError> {
+        if self.range < TOP_VALUE {
+            self.range <<= 8;
+            self.code = (self.code << 8) | u32::from(self.next_byte()?);
+        }
+
+        Ok(())
+    }
+
+    fn decode_direct_bits(&mut self, num_bits: u32) -> Result<u32, LzmaError> {
+        let mut res = 0_u32;
+
+        for _ in 0..num_bits {
+            self.range >>= 1;
+            self.code = self.code.wrapping_sub(self.range);
+            let t = 0_u32.wrapping_sub(self.code >> 31);
+            self.code = self.code.wrapping_add(self.range & t);
+
+            if self.code == self.range {
+                return Err(LzmaError::Corrupted);
+            }
+
+            self.normalize()?;
+            res = (res << 1).wrapping_add(t.wrapping_add(1));
+        }
+
+        Ok(res)
+    }
+
+    fn decode_bit(&mut self, prob: &mut u16) -> Result<u32, LzmaError> {
+        let bound = (self.range >> NUM_BIT_MODEL_TOTAL_BITS) * u32::from(*prob);
+
+        let symbol = if self.code < bound {
+            *prob += (BIT_MODEL_TOTAL - *prob) >> NUM_MOVE_BITS;
+            self.range = bound;
+
+            0
+        } else {
+            *prob -= *prob >> NUM_MOVE_BITS;
+            self.code -= bound;
+            self.range -= bound;
+
+            1
+        };
+
+        self.normalize()?;
+
+        Ok(symbol)
+    }
+
+    fn bit_tree_decode(&mut self, probs: &mut [u16], num_bits: u32) -> Result<u32, LzmaError> {
+        let mut m = 1;
+
+        for _ in 0..num_bits {
+            m = (m << 1) + self.decode_bit(&mut probs[m as usize])?;
+        }
+
+        Ok(m - (1 << num_bits))
+    }
+
+    fn bit_tree_reverse_decode(
+        &mut self,
+        probs: &mut [u16],
+        num_bits: u32,
+    ) -> Result<u32, LzmaError> {
+        let mut m = 1;
+        let mut symbol = 0;
+
+        for i in 0..num_bits {
+            let bit = self.decode_bit(&mut probs[m as usize])?;
+            m = (m << 1) + bit;
+            symbol |= bit << i;
+        }
+
+        Ok(symbol)
+    }
+}
+
+struct LenDecoder {
+    choice: u16,
+    choice2: u16,
+    low: [[u16; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+    mid: [[u16; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+    high: [u16; 1 << 8],
+}
+
+impl LenDecoder {
+    const fn new() -> Self {
+        Self {
+            choice: PROB_INIT,
+            choice2: PROB_INIT,
+            low: [[PROB_INIT; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+            mid: [[PROB_INIT; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+            high: [PROB_INIT; 1 << 8],
+        }
+    }
+
+    fn decode(&mut self, rc: &mut RangeDecoder<'_>, pos_state: usize) -> Result<usize, LzmaError> {
+        let len = if rc.decode_bit(&mut self.choice)? == 0 {
+            rc.bit_tree_decode(&mut self.low[pos_state], 3)?
+        } else if rc.decode_bit(&mut self.choice2)? == 0 {
+            8 + rc.bit_tree_decode(&mut self.mid[pos_state], 3)?
+        } else {
+            16 + rc.bit_tree_decode(&mut self.high, 8)?
+        };
+
+        Ok(len as usize)
+    }
+}
+
+struct Decoder<'a> {
+    rc: RangeDecoder<'a>,
+    lc: u32,
+    lp: u32,
+    pb: u32,
+    out: Vec<u8>,
+    limit: usize,
+    literal_probs: Vec<u16>,
+    pos_slot: [[u16; 1 << 6]; NUM_LEN_TO_POS_STATES],
+    pos_decoders: [u16; 1 + NUM_FULL_DISTANCES - END_POS_MODEL_INDEX as usize],
+    align: [u16; 1 << NUM_ALIGN_BITS],
+    is_match: [u16; NUM_STATES << NUM_POS_BITS_MAX],
+    is_rep: [u16; NUM_STATES],
+    is_rep_g0: [u16; NUM_STATES],
+    is_rep_g1: [u16; NUM_STATES],
+    is_rep_g2: [u16; NUM_STATES],
+    is_rep0_long: [u16; NUM_STATES << NUM_POS_BITS_MAX],
+    match_len: LenDecoder,
+    rep_len: LenDecoder,
+}
+
+impl<'a> Decoder<'a> {
+    fn new(rc: RangeDecoder<'a>, lc: u32, lp: u32, pb: u32, limit: usize) -> Self {
+        Self {
+            rc,
+            lc,
+            lp,
+            pb,
+            out: Vec::new(),
+            limit,
+            literal_probs: vec![PROB_INIT; 0x300 << (lc + lp)],
+            pos_slot: [[PROB_INIT; 1 << 6]; NUM_LEN_TO_POS_STATES],
+            pos_decoders: [PROB_INIT; 1 + NUM_FULL_DISTANCES - END_POS_MODEL_INDEX as usize],
+            align: [PROB_INIT; 1 << NUM_ALIGN_BITS],
+            is_match: [PROB_INIT; NUM_STATES << NUM_POS_BITS_MAX],
+            is_rep: [PROB_INIT; NUM_STATES],
+            is_rep_g0: [PROB_INIT; NUM_STATES],
+            is_rep_g1: [PROB_INIT; NUM_STATES],
+            is_rep_g2: [PROB_INIT; NUM_STATES],
+            is_rep0_long: [PROB_INIT; NUM_STATES << NUM_POS_BITS_MAX],
+            match_len: LenDecoder::new(),
+            rep_len: LenDecoder::new(),
+        }
+    }
+
+    fn decode(mut self, mut unpack_size: Option<usize>) -> Result<Vec<u8>, LzmaError> {
+        if let Some(size) = unpack_size {
+            let max_size = self.rc.input.len().saturating_mul(MAX_RESERVE_RATIO);
+            self.out.reserve_exact(size.min(max_size));
+        }
+
+        let mut state = 0;
+        let mut rep0 = 0;
+        let mut rep1 = 0;
+        let mut rep2 = 0;
+        let mut rep3 = 0;
+
+        let pb_mask = (1 << self.pb) - 1;
+
+        loop {
+            if unpack_size == Some(0) && self.rc.is_finished_ok() {
+                return Ok(self.out);
+            }
+
+            let pos_state = self.out.len() & pb_mask;
+
+            if self
+                .rc
+                .decode_bit(&mut self.is_match[(state << NUM_POS_BITS_MAX) + pos_state])?
+                == 0
+            {
+                if unpack_size == Some(0) {
+                    return Err(LzmaError::Corrupted);
+                }
+
+                self.ensure_capacity(1)?;
+                self.decode_literal(state, rep0)?;
+                state = update_state_literal(state);
+                unpack_size = unpack_size.map(|size| size - 1);
+
+                continue;
+            }
+
+            let len;
+
+            if self.rc.decode_bit(&mut self.is_rep[state])? != 0 {
+                if unpack_size == Some(0) || self.out.is_empty() {
+                    return Err(LzmaError::Corrupted);
+                }
+
+                if self.rc.decode_bit(&mut self.is_rep_g0[state])? == 0 {
+                    if self.rc.decode_bit(
+                        &mut self.is_rep0_long[(state << NUM_POS_BITS_MAX) + pos_state],
+                    )? == 0
+                    {
+                        state = if state < 7 { 9 } else { 11 };
+                        self.ensure_capacity(1)?;
+                        let byte = self.get_byte(rep0)?;
+                        self.out.push(byte);
+                        unpack_size = unpack_size.map(|size| size - 1);
+
+                        continue;
+                    }
+                } else {
+                    let dist = if self.rc.decode_bit(&mut self.is_rep_g1[state])? == 0 {
+                        rep1
+                    } else {
+                        let dist = if self.rc.decode_bit(&mut self.is_rep_g2[state])? == 0 {
+                            rep2
+                        } else {
+                            let dist = rep3;
+                            rep3 = rep2;
+
+                            dist
+                        };
+
+                        rep2 = rep1;
+
+                        dist
+                    };
+
+                    rep1 = rep0;
+                    rep0 = dist;
+                }
+
+                len = self.rep_len.decode(&mut self.rc, pos_state)?;
+                state = if state < 7 { 8 } else { 11 };
+            } else {
+                rep3 = rep2;
+                rep2 = rep1;
+                rep1 = rep0;
+                len = self.match_len.decode(&mut self.rc, pos_state)?;
+                state = if state < 7 { 7 } else { 10 };
+                rep0 = self.decode_distance(len)?;
+
+                if rep0 == u32::MAX {
+                    // End marker
+                    return if self.rc.is_finished_ok() && unpack_size.is_none_or(|size| size == 0) {
+                        Ok(self.out)
+                    } else {
+                        Err(LzmaError::Corrupted)
+                    };
+                }
+
+                if unpack_size == Some(0) || rep0 as usize >= self.out.len() {
+                    return Err(LzmaError::Corrupted);
+                }
+            }
+
+            let len = len + MATCH_MIN_LEN;
+
+            if unpack_size.is_some_and(|size| size < len) {
+                return Err(LzmaError::Corrupted);
+            }
+
+            self.ensure_capacity(len)?;
+
+            let start = self.out.len() - rep0 as usize - 1;
+
+            for i in 0..len {
+                let byte = self.out[start + i];
+                self.out.push(byte);
+            }
+
+            unpack_size = unpack_size.map(|size| size - len);
+        }
+    }
+
+    /// Check that `len` more bytes still fit within the output limit.
+    const fn ensure_capacity(&self, len: usize) -> Result<(), LzmaError> {
+        if self.limit - self.out.len() < len {
+            return Err(LzmaError::TooLarge);
+        }
+
+        Ok(())
+    }
+
+    fn get_byte(&self, dist: u32) -> Result<u8, LzmaError> {
+        let idx = self
+            .out
+            .len()
+            .checked_sub(dist as usize + 1)
+            .ok_or(LzmaError::Corrupted)?;
+
+        Ok(self.out[idx])
+    }
+
+    fn decode_literal(&mut self, state: usize, rep0: u32) -> Result<(), LzmaError> {
+        let prev_byte = self.out.last().copied().map_or(0, u32::from);
+        let total_pos = self.out.len() as u32;
+
+        let lit_state =
+            ((total_pos & ((1 << self.lp) - 1)) << self.lc) + (prev_byte >> (8 - self.lc));
+        let offset = 0x300 * lit_state as usize;
+        let probs = &mut self.literal_probs[offset..offset + 0x300];
+
+        let mut symbol = 1;
+
+        if state >= 7 {
+            let mut match_byte = {
+                let idx = self
+                    .out
+                    .len()
+                    .checked_sub(rep0 as usize + 1)
+                    .ok_or(LzmaError::Corrupted)?;
+
+                u32::from(self.out[idx])
+            };
+
+            while symbol < 0x100 {
+                let match_bit = (match_byte >> 7) & 1;
+                match_byte <<= 1;
+                let bit = self
+                    .rc
+                    .decode_bit(&mut probs[(((1 + match_bit) << 8) + symbol) as usize])?;
+                symbol = (symbol << 1) | bit;
+
+                if match_bit != bit {
+                    break;
+                }
+            }
+        }
+
+        while symbol < 0x100 {
+            symbol = (symbol << 1) | self.rc.decode_bit(&mut probs[symbol as usize])?;
+        }
+
+        self.out.push((symbol - 0x100) as u8);
+
+        Ok(())
+    }
+
+    fn decode_distance(&mut self, len: usize) -> Result<u32, LzmaError> {
+        let len_state = len.min(NUM_LEN_TO_POS_STATES - 1);
+        let pos_slot = self.rc.bit_tree_decode(&mut self.pos_slot[len_state], 6)?;
+
+        if pos_slot < START_POS_MODEL_INDEX {
+            return Ok(pos_slot);
+        }
+
+        let num_direct_bits = (pos_slot >> 1) - 1;
+        let mut dist = (2 | (pos_slot & 1)) << num_direct_bits;
+
+        if pos_slot < END_POS_MODEL_INDEX {
+            let offset = (dist - pos_slot) as usize;
+            dist += self
+                .rc
+                .bit_tree_reverse_decode(&mut self.pos_decoders[offset..], num_direct_bits)?;
+        } else {
+            dist = dist.wrapping_add(
+                self.rc
+                    .decode_direct_bits(num_direct_bits - NUM_ALIGN_BITS)?
+                    << NUM_ALIGN_BITS,
+            );
+            dist = dist.wrapping_add(
+                self.rc
+                    .bit_tree_reverse_decode(&mut self.align, NUM_ALIGN_BITS)?,
+            );
+        }
+
+        Ok(dist)
+    }
+}
+
+const fn update_state_literal(state: usize) -> usize {
+    if state < 4 {
+        0
+    } else if state < 10 {
+        state - 3
+    } else {
+        state - 6
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Generated through Python's `lzma.compress(b"hello", format=lzma.FORMAT_ALONE)`
+    const HELLO: &[u8] = &[
+        0x5d, 0x00, 0x00, 0x80, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x34,
+        0x19, 0x49, 0xee, 0x8e, 0x68, 0x21, 0xff, 0xff, 0xff, 0xb9, 0xe0, 0x00, 0x00,
+    ];
+
+    // Range coded by hand with a known size of 3: the literal `a` followed by
+    // a match of length 2 that repeats the previous byte, i.e. rep0 is 0.
+    const REP0_VALID: &[u8] = &[
+        0x5d, 0x00, 0x00, 0x80, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30,
+        0xbf, 0xfc, 0x00, 0x00, 0x00,
+    ];
+
+    // Same as `REP0_VALID` but rep0 is 1 which points before the first byte
+    const REP0_INVALID: &[u8] = &[
+        0x5d, 0x00, 0x00, 0x80, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x30,
+        0xc0, 0x04, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn end_marker() {
+        assert_eq!(decompress(HELLO).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn known_size() {
+        let mut data = HELLO.to_vec();
+        data[5..HEADER_LEN].copy_from_slice(&5_u64.to_le_bytes());
+
+        assert_eq!(decompress(&data).unwrap(), b"hello");
+
+        data[5..HEADER_LEN].copy_from_slice(&6_u64.to_le_bytes());
+
+        assert!(decompress(&data).is_err());
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(decompress(&HELLO[..10]), Err(LzmaError::UnexpectedEof));
+        assert_eq!(decompress(&HELLO[..20]), Err(LzmaError::UnexpectedEof));
+    }
+
+    #[test]
+    fn invalid_properties() {
+        let mut data = [0; HEADER_LEN + 5];
+        data[0] = 225;
+
+        assert_eq!(decompress(&data), Err(LzmaError::InvalidProperties));
+
+        let mut data = HELLO.to_vec();
+        data[0] = 9 * 5 * 5;
+
+        assert_eq!(decompress(&data), Err(LzmaError::InvalidProperties));
+    }
+
+    #[test]
+    fn oversized_header() {
+        let mut data = HELLO.to_vec();
+        data[5..HEADER_LEN].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+
+        assert_eq!(decompress(&data), Err(LzmaError::TooLarge));
+    }
+
+    #[test]
+    fn output_limit() {
+        assert_eq!(decompress_with_limit(HELLO, 5).unwrap(), b"hello");
+        assert_eq!(decompress_with_limit(HELLO, 4), Err(LzmaError::TooLarge));
+
+        // The match would exceed the limit
+        let mut data = REP0_VALID.to_vec();
+        data[5..HEADER_LEN].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(decompress_with_limit(&data, 2), Err(LzmaError::TooLarge));
+
+        // Declared size already exceeds the limit
+        assert_eq!(
+            decompress_with_limit(REP0_VALID, 2),
+            Err(LzmaError::TooLarge)
+        );
+    }
+
+    #[test]
+    fn invalid_rep0() {
+        assert_eq!(decompress(REP0_VALID).unwrap(), b"aaa");
+        assert_eq!(decompress(REP0_INVALID), Err(LzmaError::Corrupted));
+    }
+
+    #[test]
+    fn corrupted_stream() {
+        let mut data = HELLO.to_vec();
+        data[HEADER_LEN] = 1;
+
+        assert_eq!(decompress(&data), Err(LzmaError::Corrupted));
+
+        let mut data = REP0_VALID.to_vec();
+        data.truncate(data.len() - 3);
+
+        assert_eq!(decompress(&data), Err(LzmaError::UnexpectedEof));
+    }
+}
@@ -0,0 +1,605 @@
+use std::{
+    error,
+    fmt::{Display, Formatter, Result as FmtResult},
+    fs, io,
+    path::Path,
+};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::{Performance, ScoreState},
+    osu::{OsuHitErrors, OsuScoreState},
+    Beatmap, GameMods,
+};
+
+pub use self::lzma::LzmaError;
+
+use super::mode::ConvertError;
+
+mod lzma;
+
+/// A parsed `.osr` replay file.
+///
+/// # Example
+///
+/// ```no_run
+/// use rosu_pp::{Beatmap, model::replay::Replay};
+///
+/// let map = Beatmap::from_path("./map.osu").unwrap();
+/// let replay = Replay::from_path("./replay.osr").unwrap();
+///
+/// let attrs = replay.performance(&map).calculate();
+///
+/// println!("PP: {}", attrs.pp());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    /// The mode of the replay.
+    pub mode: GameMode,
+    /// The game version that created the replay.
+    pub game_version: i32,
+    /// MD5 hash of the replay's beatmap.
+    pub beatmap_md5: String,
+    /// Name of the player.
+    pub player_name: String,
+    /// MD5 hash of the replay.
+    pub replay_md5: String,
+    /// Amount of 300s.
+    pub n300: u16,
+    /// Amount of 100s (droplets for osu!catch, 150s for osu!taiko).
+    pub n100: u16,
+    /// Amount of 50s (tiny droplets for osu!catch).
+    pub n50: u16,
+    /// Amount of gekis (n320 for osu!mania).
+    pub n_geki: u16,
+    /// Amount of katus (tiny droplet misses for osu!catch, n200 for
+    /// osu!mania).
+    pub n_katu: u16,
+    /// Amount of misses.
+    pub misses: u16,
+    /// The total score.
+    pub score: i32,
+    /// The maximum combo of the play.
+    pub max_combo: u16,
+    /// Whether the play is a full combo.
+    pub perfect: bool,
+    /// Legacy bitflags of the mods.
+    pub mods: u32,
+    /// Health over time as encoded in the replay.
+    pub life_bar: String,
+    /// Time the replay was set in Windows ticks.
+    pub timestamp: i64,
+    /// All frames of the replay.
+    pub frames: Vec<ReplayFrame>,
+    /// The online id of the score, `0` if not submitted.
+    pub online_score_id: i64,
+}
+
+/// A single frame of a [`Replay`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ReplayFrame {
+    /// Time of the frame in milliseconds since the start of the map.
+    pub time: f64,
+    /// Horizontal cursor position in osu!pixels.
+    pub x: f32,
+    /// Vertical cursor position in osu!pixels.
+    pub y: f32,
+    /// Bitflags of the pressed keys.
+    pub keys: u32,
+}
+
+impl ReplayFrame {
+    const M1: u32 = 1 << 0;
+    const M2: u32 = 1 << 1;
+
+    /// Whether any key or mouse button is held down.
+    ///
+    /// Keyboard keys also set their mouse button flag so both are covered.
+    pub const fn is_pressed(&self) -> bool {
+        self.keys & (Self::M1 | Self::M2) > 0
+    }
+
+    /// Amount of keys that were newly pressed since the `prev` frame.
+    pub(crate) const fn new_presses(&self, prev: u32) -> u32 {
+        let new = self.keys & !prev;
+
+        (new & Self::M1 > 0) as u32 + (new & Self::M2 > 0) as u32
+    }
+}
+
+impl Replay {
+    /// Game versions from this value on belong to osu!lazer.
+    const LAZER_VERSION: i32 = 30_000_000;
+    /// Frame time that denotes the RNG seed frame.
+    const SEED_FRAME_TIME: i64 = -12345;
+    /// Legacy bitflag of target practice.
+    const TARGET_PRACTICE: u32 = 1 << 23;
+
+    /// Parse a [`Replay`] by providing a path to a `.osr` file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ParseReplayError> {
+        let bytes = fs::read(path)?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse a [`Replay`] by providing the content of a `.osr` file as a
+    /// slice of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseReplayError> {
+        let mut reader = Reader::new(bytes);
+
+        let mode = match reader.u8()? {
+            0 => GameMode::Osu,
+            1 => GameMode::Taiko,
+            2 => GameMode::Catch,
+            3 => GameMode::Mania,
+            mode => return Err(ParseReplayError::InvalidMode(mode)),
+        };
+
+        let game_version = reader.i32()?;
+        let beatmap_md5 = reader.string()?;
+        let player_name = reader.string()?;
+        let replay_md5 = reader.string()?;
+        let n300 = reader.u16()?;
+        let n100 = reader.u16()?;
+        let n50 = reader.u16()?;
+        let n_geki = reader.u16()?;
+        let n_katu = reader.u16()?;
+        let misses = reader.u16()?;
+        let score = reader.i32()?;
+        let max_combo = reader.u16()?;
+        let perfect = reader.u8()? != 0;
+        let mods = reader.i32()? as u32;
+        let life_bar = reader.string()?;
+        let timestamp = reader.i64()?;
+
+        let frames_len =
+            usize::try_from(reader.i32()?).map_err(|_| ParseReplayError::UnexpectedEof)?;
+        let frames = if frames_len == 0 {
+            Vec::new()
+        } else {
+            let compressed = reader.bytes(frames_len)?;
+            let decompressed = lzma::decompress(compressed)?;
+
+            Self::parse_frames(&decompressed)?
+        };
+
+        let online_score_id = reader.i64()?;
+
+        if mods & Self::TARGET_PRACTICE > 0 {
+            // Accuracy of target practice; irrelevant for calculations.
+            let _ = reader.bytes(8)?;
+        }
+
+        Ok(Self {
+            mode,
+            game_version,
+            beatmap_md5,
+            player_name,
+            replay_md5,
+            n300,
+            n100,
+            n50,
+            n_geki,
+            n_katu,
+            misses,
+            score,
+            max_combo,
+            perfect,
+            mods,
+            life_bar,
+            timestamp,
+            frames,
+            online_score_id,
+        })
+    }
+
+    fn parse_frames(bytes: &[u8]) -> Result<Vec<ReplayFrame>, ParseReplayError> {
+        let content = std::str::from_utf8(bytes).map_err(|_| ParseReplayError::InvalidFrame)?;
+
+        let mut frames = Vec::with_capacity(content.len() / 16);
+        let mut time = 0;
+
+        for frame in content.split(',').filter(|frame| !frame.is_empty()) {
+            let mut split = frame.split('|');
+
+            let mut next = || split.next().ok_or(ParseReplayError::InvalidFrame);
+
+            let delta: i64 = next()?
+                .parse()
+                .map_err(|_| ParseReplayError::InvalidFrame)?;
+
+            if delta == Self::SEED_FRAME_TIME {
+                continue;
+            }
+
+            let x: f32 = next()?
+                .parse()
+                .map_err(|_| ParseReplayError::InvalidFrame)?;
+            let y: f32 = next()?
+                .parse()
+                .map_err(|_| ParseReplayError::InvalidFrame)?;
+            let keys: f64 = next()?
+                .parse()
+                .map_err(|_| ParseReplayError::InvalidFrame)?;
+
+            time += delta;
+
+            frames.push(ReplayFrame {
+                time: time as f64,
+                x,
+                y,
+                keys: keys as u32,
+            });
+        }
+
+        Ok(frames)
+    }
+
+    /// Whether the replay was created by osu!lazer.
+    pub const fn is_lazer(&self) -> bool {
+        self.game_version >= Self::LAZER_VERSION
+    }
+
+    /// The mods of the replay.
+    pub fn mods(&self) -> GameMods {
+        self.mods.into()
+    }
+
+    /// The replay's hit results as [`ScoreState`].
+    ///
+    /// Replays do not store tick or slider end hits so those fields are `0`.
+    /// This is only accurate for osu!stable scores; for osu!lazer scores
+    /// those fields should be specified separately if known.
+    pub fn score_state(&self) -> ScoreState {
+        ScoreState {
+            max_combo: u32::from(self.max_combo),
+            osu_large_tick_hits: 0,
+            osu_small_tick_hits: 0,
+            slider_end_hits: 0,
+            n_geki: u32::from(self.n_geki),
+            n_katu: u32::from(self.n_katu),
+            n300: u32::from(self.n300),
+            n100: u32::from(self.n100),
+            n50: u32::from(self.n50),
+            misses: u32::from(self.misses),
+        }
+    }
+
+    /// The replay's hit results as [`OsuScoreState`].
+    pub fn osu_score_state(&self) -> OsuScoreState {
+        self.score_state().into()
+    }
+
+    /// Match the replay's frames against the map's hit objects to determine
+    /// the hit offset of each object.
+    ///
+    /// Returns an error if the map cannot be converted to osu!standard.
+    pub fn osu_hit_errors(&self, map: &Beatmap) -> Result<OsuHitErrors, ConvertError> {
+        crate::osu::hit_errors(self, map)
+    }
+
+    /// Create a performance calculator for the replay on the given map.
+    ///
+    /// The calculator's mods, hit results, and combo are taken from the
+    /// replay. Tick and slider end hits are not stored in replays so they
+    /// remain unspecified. For osu!standard replays, the unstable rate is
+    /// taken from the matched replay frames if possible.
+    pub fn performance<'map>(&self, map: &'map Beatmap) -> Performance<'map> {
+        let performance = Performance::new(map)
+            .mode_or_ignore(self.mode)
+            .mods(self.mods)
+            .lazer(self.is_lazer())
+            .combo(u32::from(self.max_combo))
+            .n_geki(u32::from(self.n_geki))
+            .n_katu(u32::from(self.n_katu))
+            .n300(u32::from(self.n300))
+            .n100(u32::from(self.n100))
+            .n50(u32::from(self.n50))
+            .misses(u32::from(self.misses));
+
+        let Performance::Osu(osu) = performance else {
+            return performance;
+        };
+
+        let unstable_rate = self
+            .osu_hit_errors(map)
+            .ok()
+            .and_then(|hit_errors| hit_errors.unstable_rate());
+
+        match unstable_rate {
+            Some(unstable_rate) => Performance::Osu(osu.unstable_rate(unstable_rate)),
+            None => Performance::Osu(osu),
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    const fn bytes(&mut self, len: usize) -> Result<&'a [u8], ParseReplayError> {
+        if self.bytes.len() < len {
+            return Err(ParseReplayError::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ParseReplayError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+
+        Ok(array)
+    }
+
+    fn u8(&mut self) -> Result<u8, ParseReplayError> {
+        self.array().map(u8::from_le_bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16, ParseReplayError> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, ParseReplayError> {
+        self.array().map(i32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, ParseReplayError> {
+        self.array().map(i64::from_le_bytes)
+    }
+
+    fn uleb128(&mut self) -> Result<usize, ParseReplayError> {
+        let mut value = 0;
+        let mut shift = 0;
+
+        loop {
+            let byte = self.u8()?;
+
+            if shift >= usize::BITS {
+                return Err(ParseReplayError::InvalidString);
+            }
+
+            value |= usize::from(byte & 0x7F) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+
+            shift += 7;
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseReplayError> {
+        match self.u8()? {
+            0x00 => Ok(String::new()),
+            0x0B => {
+                let len = self.uleb128()?;
+                let bytes = self.bytes(len)?;
+
+                String::from_utf8(bytes.to_vec()).map_err(|_| ParseReplayError::InvalidString)
+            }
+            _ => Err(ParseReplayError::InvalidString),
+        }
+    }
+}
+
+/// All the ways that parsing a [`Replay`] can fail.
+#[derive(Debug)]
+pub enum ParseReplayError {
+    /// Failed to read the replay file.
+    Io(io::Error),
+    /// A replay frame is not of the form `delta|x|y|keys`.
+    InvalidFrame,
+    /// The mode byte does not denote any known mode.
+    InvalidMode(u8),
+    /// A string is not properly prefixed or not valid UTF-8.
+    InvalidString,
+    /// The compressed replay frames could not be decompressed.
+    Lzma(LzmaError),
+    /// The replay ended prematurely.
+    UnexpectedEof,
+}
+
+impl error::Error for ParseReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ParseReplayError::Io(err) => Some(err),
+            ParseReplayError::Lzma(err) => Some(err),
+            ParseReplayError::InvalidFrame
+            | ParseReplayError::InvalidMode(_)
+            | ParseReplayError::InvalidString
+            | ParseReplayError::UnexpectedEof => None,
+        }
+    }
+}
+
+impl Display for ParseReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Io(_) => f.write_str("failed to read replay"),
+            Self::InvalidFrame => f.write_str("invalid replay frame"),
+            Self::InvalidMode(mode) => write!(f, "invalid mode {mode}"),
+            Self::InvalidString => f.write_str("invalid string"),
+            Self::Lzma(_) => f.write_str("failed to decompress replay frames"),
+            Self::UnexpectedEof => f.write_str("unexpected end of replay"),
+        }
+    }
+}
+
+impl From<io::Error> for ParseReplayError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<LzmaError> for ParseReplayError {
+    fn from(err: LzmaError) -> Self {
+        Self::Lzma(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{any::PerformanceAttributes, osu::OsuPerformance};
+
+    use super::*;
+
+    // Synthetic DT+HD replay on map 2785319, written with Python's `lzma`
+    // module (`FORMAT_ALONE`). Each non-spinner object is pressed once at a
+    // known offset with the cursor on the object.
+    fn replay() -> Replay {
+        Replay::from_path("./resources/2785319.osr").unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let replay = replay();
+
+        assert_eq!(replay.mode, GameMode::Osu);
+        assert_eq!(replay.game_version, 20240101);
+        assert_eq!(replay.beatmap_md5, "3ca9a5e23c9a4e9332f4f6e2a48865f7");
+        assert_eq!(replay.player_name, "rosu");
+        assert!(replay.replay_md5.is_empty());
+        assert_eq!(replay.max_combo, 700);
+        assert!(!replay.perfect);
+        assert_eq!(replay.online_score_id, 1234567);
+        assert!(!replay.is_lazer());
+
+        // Two leading frames plus a press and release for each of the 600
+        // non-spinner objects; the seed frame is skipped.
+        assert_eq!(replay.frames.len(), 1202);
+        assert!((replay.frames[0].time - 0.0).abs() < f64::EPSILON);
+        assert!((replay.frames[1].time - -1.0).abs() < f64::EPSILON);
+        assert!(replay.frames[2].is_pressed());
+        assert!(!replay.frames[3].is_pressed());
+    }
+
+    #[test]
+    fn score_state() {
+        let replay = replay();
+
+        let state = replay.score_state();
+
+        assert_eq!(state.n300, 590);
+        assert_eq!(state.n100, 8);
+        assert_eq!(state.n50, 2);
+        assert_eq!(state.n_geki, 120);
+        assert_eq!(state.n_katu, 5);
+        assert_eq!(state.misses, 1);
+        assert_eq!(state.max_combo, 700);
+
+        let mods = replay.mods();
+        assert!((mods.clock_rate() - 1.5).abs() < f64::EPSILON);
+        assert!(mods.hd() && !mods.hr());
+    }
+
+    #[test]
+    fn invalid() {
+        let bytes = std::fs::read("./resources/2785319.osr").unwrap();
+
+        assert!(matches!(
+            Replay::from_bytes(&bytes[..100]),
+            Err(ParseReplayError::UnexpectedEof)
+        ));
+
+        let mut invalid_mode = bytes.clone();
+        invalid_mode[0] = 4;
+
+        assert!(matches!(
+            Replay::from_bytes(&invalid_mode),
+            Err(ParseReplayError::InvalidMode(4))
+        ));
+
+        let mut invalid_string = bytes;
+        invalid_string[5] = 1;
+
+        assert!(matches!(
+            Replay::from_bytes(&invalid_string),
+            Err(ParseReplayError::InvalidString)
+        ));
+    }
+
+    #[test]
+    fn performance() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let replay = replay();
+
+        let PerformanceAttributes::Osu(attrs) = replay.performance(&map).calculate() else {
+            panic!("expected osu attributes");
+        };
+
+        let expected = OsuPerformance::from(&map)
+            .mods(replay.mods)
+            .lazer(false)
+            .state(replay.osu_score_state())
+            .unstable_rate(
+                replay
+                    .osu_hit_errors(&map)
+                    .unwrap()
+                    .unstable_rate()
+                    .unwrap(),
+            )
+            .calculate()
+            .unwrap();
+
+        assert!((attrs.pp - expected.pp).abs() < f64::EPSILON);
+
+        let unstable_rate = attrs.estimated_unstable_rate.unwrap();
+        assert!((unstable_rate - 54.543_560_573_178_574).abs() < 1e-9);
+    }
+
+    #[test]
+    fn performance_lazer() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut bytes = std::fs::read("./resources/2785319.osr").unwrap();
+        bytes[1..5].copy_from_slice(&30_000_001_i32.to_le_bytes());
+        let replay = Replay::from_bytes(&bytes).unwrap();
+
+        assert!(replay.is_lazer());
+
+        let PerformanceAttributes::Osu(attrs) = replay.performance(&map).calculate() else {
+            panic!("expected osu attributes");
+        };
+
+        let unstable_rate = replay
+            .osu_hit_errors(&map)
+            .unwrap()
+            .unstable_rate()
+            .unwrap();
+
+        let expected = OsuPerformance::from(&map)
+            .mods(replay.mods)
+            .lazer(true)
+            .combo(u32::from(replay.max_combo))
+            .n300(u32::from(replay.n300))
+            .n100(u32::from(replay.n100))
+            .n50(u32::from(replay.n50))
+            .misses(u32::from(replay.misses))
+            .unstable_rate(unstable_rate)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(attrs, expected);
+
+        // Treating the missing slider ends as dropped undervalues the play
+        let dropped_ends = OsuPerformance::from(&map)
+            .mods(replay.mods)
+            .lazer(true)
+            .state(replay.osu_score_state())
+            .unstable_rate(unstable_rate)
+            .calculate()
+            .unwrap();
+
+        assert!(attrs.pp > dropped_ends.pp);
+    }
+}
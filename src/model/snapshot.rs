@@ -0,0 +1,584 @@
+use std::{
+    collections::VecDeque,
+    error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{hit_object::HitObjectKind, mode::ConvertError},
+    util::strains_vec::StrainsVec,
+    Beatmap, Difficulty,
+};
+
+const MAGIC: [u8; 4] = *b"rpps";
+const FORMAT_VERSION: u8 = 1;
+
+/// Serialize and deserialize the processing state of a gradual calculator.
+///
+/// Deserialization happens in-place so that values which only depend on the
+/// map and difficulty settings don't need to be part of the snapshot.
+pub(crate) trait SnapshotState {
+    fn write(&self, writer: &mut SnapshotWriter);
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError>;
+}
+
+/// Serialize a gradual calculator's state into a snapshot.
+pub(crate) fn to_snapshot<T: SnapshotState>(state: &T, mode: GameMode, checksum: u64) -> Vec<u8> {
+    let mut writer = SnapshotWriter::new(mode, checksum);
+    state.write(&mut writer);
+
+    writer.into_bytes()
+}
+
+/// Restore a gradual calculator's state from a snapshot.
+pub(crate) fn from_snapshot<T: SnapshotState>(
+    state: &mut T,
+    mode: GameMode,
+    checksum: u64,
+    snapshot: &[u8],
+) -> Result<(), SnapshotError> {
+    let mut reader = SnapshotReader::new(snapshot, mode, checksum)?;
+    state.read(&mut reader)?;
+
+    reader.finish()
+}
+
+/// Collects the bytes of a snapshot.
+pub(crate) struct SnapshotWriter {
+    bytes: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    fn new(mode: GameMode, checksum: u64) -> Self {
+        let mut bytes = Vec::with_capacity(4096);
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.push(mode as u8);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        Self { bytes }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    fn extend(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+}
+
+/// Reads the bytes of a snapshot.
+pub(crate) struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> SnapshotReader<'a> {
+    /// Validate the snapshot's header and prepare reading its state.
+    fn new(bytes: &'a [u8], mode: GameMode, checksum: u64) -> Result<Self, SnapshotError> {
+        let actual_mode = Self::mode(bytes)?;
+
+        if actual_mode != mode {
+            return Err(SnapshotError::ModeMismatch {
+                expected: mode,
+                actual: actual_mode,
+            });
+        }
+
+        let mut reader = Self {
+            bytes: &bytes[MAGIC.len() + 2..],
+        };
+
+        if u64::from_le_bytes(reader.array()?) != checksum {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+
+        Ok(reader)
+    }
+
+    /// The [`GameMode`] that a snapshot was taken on.
+    pub(crate) fn mode(bytes: &[u8]) -> Result<GameMode, SnapshotError> {
+        match bytes {
+            [m0, m1, m2, m3, FORMAT_VERSION, mode, ..] if [*m0, *m1, *m2, *m3] == MAGIC => {
+                match mode {
+                    0 => Ok(GameMode::Osu),
+                    1 => Ok(GameMode::Taiko),
+                    2 => Ok(GameMode::Catch),
+                    3 => Ok(GameMode::Mania),
+                    _ => Err(SnapshotError::InvalidHeader),
+                }
+            }
+            _ => Err(SnapshotError::InvalidHeader),
+        }
+    }
+
+    /// Ensure that all bytes have been read.
+    const fn finish(self) -> Result<(), SnapshotError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(SnapshotError::InvalidState)
+        }
+    }
+
+    const fn bytes(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.bytes.len() < len {
+            return Err(SnapshotError::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+
+        Ok(array)
+    }
+
+    /// Read a length and make sure that the remaining bytes could hold that
+    /// many values.
+    fn read_len(&mut self) -> Result<usize, SnapshotError> {
+        let mut len = 0_usize;
+        len.read(self)?;
+
+        if len > self.bytes.len() {
+            return Err(SnapshotError::UnexpectedEof);
+        }
+
+        Ok(len)
+    }
+}
+
+macro_rules! impl_snapshot_state_num {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl SnapshotState for $ty {
+                fn write(&self, writer: &mut SnapshotWriter) {
+                    writer.extend(&self.to_le_bytes());
+                }
+
+                fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+                    *self = <$ty>::from_le_bytes(reader.array()?);
+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_state_num!(u8, i32, u32, u64, f32, f64);
+
+impl SnapshotState for usize {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        (*self as u64).write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let mut value = 0_u64;
+        value.read(reader)?;
+        *self = usize::try_from(value).map_err(|_| SnapshotError::InvalidState)?;
+
+        Ok(())
+    }
+}
+
+impl SnapshotState for bool {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        u8::from(*self).write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let mut value = 0_u8;
+        value.read(reader)?;
+
+        *self = match value {
+            0 => false,
+            1 => true,
+            _ => return Err(SnapshotError::InvalidState),
+        };
+
+        Ok(())
+    }
+}
+
+impl<T: SnapshotState + Default> SnapshotState for Option<T> {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.is_some().write(writer);
+
+        if let Some(value) = self {
+            value.write(writer);
+        }
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let mut is_some = false;
+        is_some.read(reader)?;
+
+        *self = if is_some {
+            let mut value = T::default();
+            value.read(reader)?;
+
+            Some(value)
+        } else {
+            None
+        };
+
+        Ok(())
+    }
+}
+
+impl<T: SnapshotState + Default> SnapshotState for Vec<T> {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.len().write(writer);
+
+        for value in self.iter() {
+            value.write(writer);
+        }
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let len = reader.read_len()?;
+        self.clear();
+        self.resize_with(len, T::default);
+
+        self.iter_mut().try_for_each(|value| value.read(reader))
+    }
+}
+
+impl<T: SnapshotState + Default> SnapshotState for VecDeque<T> {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.len().write(writer);
+
+        for value in self.iter() {
+            value.write(writer);
+        }
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let len = reader.read_len()?;
+        self.clear();
+        self.resize_with(len, T::default);
+
+        self.iter_mut().try_for_each(|value| value.read(reader))
+    }
+}
+
+// Boxed slices are sized by the map so their length must not change.
+impl<T: SnapshotState> SnapshotState for Box<[T]> {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.len().write(writer);
+
+        for value in self.iter() {
+            value.write(writer);
+        }
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        if reader.read_len()? != self.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+
+        self.iter_mut().try_for_each(|value| value.read(reader))
+    }
+}
+
+impl SnapshotState for StrainsVec {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.len().write(writer);
+
+        for value in self.iter() {
+            value.write(writer);
+        }
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let len = reader.read_len()?;
+        let mut strains = Self::with_capacity(len);
+
+        for _ in 0..len {
+            let mut value = 0.0;
+            value.read(reader)?;
+            strains.push(value);
+        }
+
+        *self = strains;
+
+        Ok(())
+    }
+}
+
+/// Checksum over a [`Beatmap`] and the [`Difficulty`] settings.
+///
+/// Uses FNV-1a so that the value is stable across platforms and compiler
+/// versions.
+pub(crate) fn checksum(map: &Beatmap, difficulty: &Difficulty) -> u64 {
+    let mut hasher = Fnv1a::new();
+
+    hasher.i32(map.version);
+    hasher.bool(map.is_convert);
+    hasher.f32(map.stack_leniency);
+    hasher.u8(map.mode as u8);
+    hasher.f32(map.ar);
+    hasher.f32(map.cs);
+    hasher.f32(map.hp);
+    hasher.f32(map.od);
+    hasher.f64(map.slider_multiplier);
+    hasher.f64(map.slider_tick_rate);
+
+    hasher.usize(map.breaks.len());
+
+    for b in map.breaks.iter() {
+        hasher.f64(b.start_time);
+        hasher.f64(b.end_time);
+    }
+
+    hasher.usize(map.timing_points.len());
+
+    for tp in map.timing_points.iter() {
+        hasher.f64(tp.time);
+        hasher.f64(tp.beat_len);
+    }
+
+    hasher.usize(map.difficulty_points.len());
+
+    for dp in map.difficulty_points.iter() {
+        hasher.f64(dp.time);
+        hasher.f64(dp.slider_velocity);
+        hasher.f64(dp.bpm_multiplier);
+        hasher.bool(dp.generate_ticks);
+    }
+
+    hasher.usize(map.effect_points.len());
+
+    for ep in map.effect_points.iter() {
+        hasher.f64(ep.time);
+        hasher.bool(ep.kiai);
+        hasher.f64(ep.scroll_speed);
+    }
+
+    hasher.usize(map.hit_objects.len());
+
+    for h in map.hit_objects.iter() {
+        hasher.f32(h.pos.x);
+        hasher.f32(h.pos.y);
+        hasher.f64(h.start_time);
+
+        match h.kind {
+            HitObjectKind::Circle => hasher.u8(0),
+            HitObjectKind::Slider(ref slider) => {
+                hasher.u8(1);
+                hasher.f64(slider.expected_dist.unwrap_or(-1.0));
+                hasher.usize(slider.repeats);
+                hasher.usize(slider.control_points.len());
+
+                for point in slider.control_points.iter() {
+                    hasher.f32(point.pos.x);
+                    hasher.f32(point.pos.y);
+
+                    match point.path_type {
+                        Some(path_type) => {
+                            hasher.u8(path_type.kind as u8 + 1);
+                            hasher.i32(path_type.degree.map_or(0, i32::from));
+                        }
+                        None => hasher.u8(0),
+                    }
+                }
+            }
+            HitObjectKind::Spinner(ref spinner) => {
+                hasher.u8(2);
+                hasher.f64(spinner.duration);
+            }
+            HitObjectKind::Hold(ref hold) => {
+                hasher.u8(3);
+                hasher.f64(hold.duration);
+            }
+        }
+    }
+
+    for sound in map.hit_sounds.iter() {
+        hasher.u8(u8::from(*sound));
+    }
+
+    let attrs = map.attributes().difficulty(difficulty).build();
+
+    hasher.f64(attrs.ar);
+    hasher.f64(attrs.od);
+    hasher.f64(attrs.cs);
+    hasher.f64(attrs.hp);
+    hasher.u32(difficulty.get_mods().bits());
+    hasher.i32(difficulty.get_mods().random_seed().unwrap_or(0));
+    hasher.f64(difficulty.get_clock_rate());
+    hasher.usize(difficulty.get_passed_objects());
+    hasher.bool(difficulty.get_hardrock_offsets());
+    hasher.bool(difficulty.get_lazer());
+
+    hasher.finish()
+}
+
+struct Fnv1a {
+    hash: u64,
+}
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01B3;
+
+    const fn new() -> Self {
+        Self {
+            hash: Self::OFFSET_BASIS,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.hash ^= u64::from(byte);
+            self.hash = self.hash.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    fn i32(&mut self, value: i32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+
+    fn f32(&mut self, value: f32) {
+        self.write(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.write(&value.to_le_bytes());
+    }
+
+    const fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/// All the ways that restoring a gradual calculator from a snapshot can fail.
+#[derive(Copy, Clone, Debug)]
+pub enum SnapshotError {
+    /// The map could not be converted to the snapshot's mode.
+    Convert(ConvertError),
+    /// The snapshot was taken on a different map or with different difficulty
+    /// settings.
+    ChecksumMismatch,
+    /// The bytes are not a snapshot or the snapshot's format is not supported.
+    InvalidHeader,
+    /// The processing state does not fit the map.
+    InvalidState,
+    /// The snapshot was taken on a different mode.
+    ModeMismatch {
+        expected: GameMode,
+        actual: GameMode,
+    },
+    /// The snapshot ended unexpectedly.
+    UnexpectedEof,
+}
+
+impl error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            SnapshotError::Convert(err) => Some(err),
+            SnapshotError::ChecksumMismatch
+            | SnapshotError::InvalidHeader
+            | SnapshotError::InvalidState
+            | SnapshotError::ModeMismatch { .. }
+            | SnapshotError::UnexpectedEof => None,
+        }
+    }
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Self::Convert(_) => f.write_str("failed to convert map"),
+            Self::ChecksumMismatch => {
+                f.write_str("snapshot was taken on a different map or difficulty")
+            }
+            Self::InvalidHeader => f.write_str("invalid snapshot header"),
+            Self::InvalidState => f.write_str("invalid snapshot state"),
+            Self::ModeMismatch { expected, actual } => {
+                write!(f, "expected snapshot of mode {expected:?}, got {actual:?}")
+            }
+            Self::UnexpectedEof => f.write_str("unexpected end of snapshot"),
+        }
+    }
+}
+
+impl From<ConvertError> for SnapshotError {
+    fn from(err: ConvertError) -> Self {
+        Self::Convert(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{osu::OsuGradualDifficulty, taiko::TaikoGradualDifficulty};
+
+    use super::*;
+
+    #[test]
+    fn invalid() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut other_map = map.clone();
+        other_map.hit_objects[500].start_time += 1.0;
+
+        let mut gradual = OsuGradualDifficulty::new(Difficulty::new(), &map).unwrap();
+        gradual.nth(9);
+        let snapshot = gradual.to_snapshot();
+
+        let restore = |map: &Beatmap, snapshot: &[u8]| {
+            OsuGradualDifficulty::from_snapshot(Difficulty::new(), map, snapshot).err()
+        };
+
+        assert!(restore(&map, &snapshot).is_none());
+
+        assert!(matches!(
+            restore(&map, &[]),
+            Some(SnapshotError::InvalidHeader)
+        ));
+        assert!(matches!(
+            restore(&map, &snapshot[..snapshot.len() - 1]),
+            Some(SnapshotError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            restore(&map, &[&snapshot[..], &[0]].concat()),
+            Some(SnapshotError::InvalidState)
+        ));
+        assert!(matches!(
+            restore(&other_map, &snapshot),
+            Some(SnapshotError::ChecksumMismatch)
+        ));
+        assert!(matches!(
+            TaikoGradualDifficulty::from_snapshot(Difficulty::new(), &map, &snapshot),
+            Err(SnapshotError::ModeMismatch {
+                expected: GameMode::Taiko,
+                actual: GameMode::Osu,
+            })
+        ));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{model::beatmap::BeatmapAttributesBuilder, osu::performance::OsuPerformance};
+use crate::{
+    model::beatmap::BeatmapAttributesBuilder,
+    osu::{performance::OsuPerformance, style::OsuMapStyle},
+};
 
 /// The result of a difficulty calculation on an osu!standard map.
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -13,22 +16,45 @@ pub struct OsuDifficultyAttributes {
     pub flow: f64,
     /// The difficulty of the precision skill.
     pub precision: f64,
+    /// The difficulty of reading overlapping approach circles on low
+    /// approach rates.
+    ///
+    /// Not included in [`OsuDifficultyAttributes::aim`] but rewarded as its
+    /// own portion of the pp.
+    pub reading: f64,
     /// The difficulty of the speed skill.
     pub speed: f64,
     /// The difficulty of the stamina skill.
     pub stamina: f64,
     /// The difficulty of the accuracy skill.
     pub accuracy: f64,
+    /// The difficulty of the flashlight skill.
+    ///
+    /// Also calculated without the FL mod but only relevant with it.
+    pub flashlight: f64,
     /// Weighted sum of aim strains.
     pub aim_difficult_strain_count: f64,
     /// Weighted sum of jump aim strains.
     pub jump_aim_difficult_strain_count: f64,
     /// Weighted sum of flow aim strains.
     pub flow_aim_difficult_strain_count: f64,
+    /// Weighted sum of precision strains.
+    pub precision_difficult_strain_count: f64,
     /// Weighted sum of speed strains.
     pub speed_difficult_strain_count: f64,
     /// Weighted sum of stamina strains.
     pub stamina_difficult_strain_count: f64,
+    /// Weighted sum of flashlight strains.
+    pub flashlight_difficult_strain_count: f64,
+    /// Average flow of all objects, ranging from `0.0` (only jump aim) to
+    /// `1.0` (only flow aim).
+    pub flow_ratio: f64,
+    /// Average normalized distance between consecutive objects.
+    pub mean_jump_dist: f64,
+    /// Share of objects that are mostly flow aim.
+    pub stream_share: f64,
+    /// Share of objects that are mostly jump aim.
+    pub jump_share: f64,
     /// The approach rate.
     pub ar: f64,
     /// The great hit window.
@@ -60,6 +86,41 @@ pub struct OsuDifficultyAttributes {
     pub max_combo: u32,
 }
 
+snapshot_state!(OsuDifficultyAttributes {
+    aim,
+    aim_difficult_slider_count,
+    jump,
+    flow,
+    precision,
+    reading,
+    speed,
+    stamina,
+    accuracy,
+    flashlight,
+    aim_difficult_strain_count,
+    jump_aim_difficult_strain_count,
+    flow_aim_difficult_strain_count,
+    precision_difficult_strain_count,
+    speed_difficult_strain_count,
+    stamina_difficult_strain_count,
+    flashlight_difficult_strain_count,
+    flow_ratio,
+    mean_jump_dist,
+    stream_share,
+    jump_share,
+    ar,
+    great_hit_window,
+    ok_hit_window,
+    meh_hit_window,
+    hp,
+    n_circles,
+    n_sliders,
+    n_large_ticks,
+    n_spinners,
+    stars,
+    max_combo,
+});
+
 impl OsuDifficultyAttributes {
     /// Return the maximum combo.
     pub const fn max_combo(&self) -> u32 {
@@ -76,6 +137,11 @@ impl OsuDifficultyAttributes {
         BeatmapAttributesBuilder::osu_great_hit_window_to_od(self.great_hit_window)
     }
 
+    /// Classify the map's style based on its ratings and object shares.
+    pub fn style(&self) -> OsuMapStyle {
+        OsuMapStyle::classify(self)
+    }
+
     /// Returns a builder for performance calculation.
     pub fn performance<'a>(self) -> OsuPerformance<'a> {
         self.into()
@@ -97,14 +163,24 @@ pub struct OsuPerformanceAttributes {
     pub pp_flow_aim: f64,
     /// The precision portion of the final pp.
     pub pp_precision: f64,
+    /// The reading portion of the final pp, `0.0` from AR8 on.
+    pub pp_reading: f64,
     /// The speed portion of the final pp.
     pub pp_speed: f64,
     /// The stamina portion of the final pp.
     pub pp_stamina: f64,
+    /// The flashlight portion of the final pp, `0.0` without FL.
+    pub pp_flashlight: f64,
     /// The acc portion of the final pp.
     pub pp_acc: f64,
     /// Misses including an approximated amount of slider breaks
     pub effective_miss_count: f64,
+    /// The unstable rate that was used for the calculation, either as
+    /// specified or estimated through the amount of 300s.
+    ///
+    /// `None` if no unstable rate was specified and there were no 300s to
+    /// estimate it from.
+    pub estimated_unstable_rate: Option<f64>,
 }
 
 impl OsuPerformanceAttributes {
@@ -138,3 +214,93 @@ impl From<OsuPerformanceAttributes> for OsuDifficultyAttributes {
         attributes.difficulty
     }
 }
+
+/// Every factor that went into a performance calculation on an osu!standard
+/// map.
+///
+/// Each skill's pp value is the product of its [`OsuSkillBreakdown`] factors,
+/// the skill group's weights, and for aim skills the slider nerf factor.
+/// All skills but stamina and flashlight are also multiplied by the length
+/// bonus. Flashlight uses the aim weights without the slider nerf factor and
+/// is only valued with the FL mod.
+///
+/// The final pp are the 1.1-norm of the aim value, the maximum of the speed
+/// and stamina value, the flashlight value, and the accuracy value,
+/// multiplied by the base, NF, and SO multipliers.
+///
+/// Returned by [`OsuPerformance::explain`]. If the score has no hits, all
+/// factors are zero.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuPerformanceBreakdown {
+    /// The resulting performance attributes.
+    pub attributes: OsuPerformanceAttributes,
+    /// Multiplier applied to the final pp of every score.
+    pub base_multiplier: f64,
+    /// Multiplier for the NF mod, `1.0` without NF.
+    pub no_fail_multiplier: f64,
+    /// Multiplier for the SO mod, `1.0` without SO.
+    pub spun_out_multiplier: f64,
+    /// Misses including an approximated amount of slider breaks before the
+    /// relax adjustment.
+    pub effective_miss_count: f64,
+    /// Misses that were added to the effective miss count for 100s and 50s
+    /// on the RX mod, `0.0` without RX.
+    pub relax_miss_adjustment: f64,
+    /// The standard deviation of hit errors, either as specified or
+    /// estimated through the amount of 300s.
+    pub normalized_hit_error: f64,
+    /// Accuracy weight of the aim and reading skills.
+    pub aim_accuracy_weight: f64,
+    /// Combo weight of the aim and reading skills, `1.0` for the combo
+    /// scaling rework.
+    pub aim_combo_weight: f64,
+    /// Length weight of the flashlight skill for the FL mod, `1.0` without
+    /// FL.
+    pub flashlight_length_weight: f64,
+    /// Factor for improperly followed sliders on the aim skills.
+    pub slider_nerf_factor: f64,
+    /// Accuracy weight of the speed and stamina skills.
+    pub speed_accuracy_weight: f64,
+    /// Combo weight of the speed and stamina skills, `1.0` for the combo
+    /// scaling rework.
+    pub speed_combo_weight: f64,
+    /// Length bonus of all skills but stamina, `1.0` if disabled.
+    pub length_bonus: f64,
+    /// The aim skill.
+    pub aim: OsuSkillBreakdown,
+    /// The jump aim skill.
+    pub jump_aim: OsuSkillBreakdown,
+    /// The flow aim skill.
+    pub flow_aim: OsuSkillBreakdown,
+    /// The precision skill.
+    pub precision: OsuSkillBreakdown,
+    /// The reading skill whose base value is what it adds onto the base
+    /// value of the aim skill.
+    pub reading: OsuSkillBreakdown,
+    /// The speed skill.
+    pub speed: OsuSkillBreakdown,
+    /// The stamina skill.
+    pub stamina: OsuSkillBreakdown,
+    /// The flashlight skill.
+    pub flashlight: OsuSkillBreakdown,
+    /// The accuracy value before any weights, based on the normalized hit
+    /// error and the accuracy rating.
+    pub accuracy_base_value: f64,
+    /// Length weight of the accuracy value.
+    pub accuracy_length_weight: f64,
+    /// Weight of the accuracy value for the HD and FL mods.
+    pub accuracy_mod_weight: f64,
+}
+
+/// The factors of a single skill within an [`OsuPerformanceBreakdown`].
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct OsuSkillBreakdown {
+    /// The skill's value based on its difficulty rating before any weights.
+    pub base_value: f64,
+    /// The misses that the miss weight is based on, considering the position
+    /// of misses if known.
+    pub miss_count: f64,
+    /// The weight for misses, either through the combo scaling rework or the
+    /// classic miss penalty.
+    pub miss_weight: f64,
+}
@@ -0,0 +1,144 @@
+/// Runtime configuration of the PP+ performance algorithm.
+///
+/// The default value reproduces the regular PP+ calculation. Individual
+/// behaviours can be toggled to compare results, e.g. the combo scaling
+/// rework against the classic combo weighting.
+///
+/// The configuration is stored on [`Difficulty`] so that it is carried
+/// through [`OsuPerformance`] and gradual calculations.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::osu::{OsuPerformance, PpPlusConfig};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let config = PpPlusConfig {
+///     combo_scaling_rework: false,
+///     ..PpPlusConfig::default()
+/// };
+///
+/// let attrs = OsuPerformance::new(&map)
+///     .pplus_config(config)
+///     .combo(500)
+///     .calculate()
+///     .unwrap();
+/// ```
+///
+/// [`Difficulty`]: crate::Difficulty
+/// [`OsuPerformance`]: crate::osu::OsuPerformance
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PpPlusConfig {
+    /// Whether misses are weighted w.r.t. each skill's difficult strain count
+    /// (combo scaling rework) instead of through the score's max combo.
+    pub combo_scaling_rework: bool,
+    /// Whether slider breaks should be estimated through the score's max
+    /// combo on top of the actual misses.
+    pub effective_miss_count: bool,
+    /// Whether the aim, precision, and speed values receive a length bonus.
+    pub length_bonus: bool,
+    /// Base multiplier of the combo scaling rework's miss weight.
+    pub miss_weight_base: f64,
+    /// Exponent of the logarithm of the difficult strain count in the combo
+    /// scaling rework's miss weight.
+    pub csr_exponent: f64,
+    /// Base value of the length bonus.
+    pub length_bonus_base: f64,
+    /// Linear length bonus that is reached at [`length_bonus_hits`] hits.
+    ///
+    /// [`length_bonus_hits`]: PpPlusConfig::length_bonus_hits
+    pub length_bonus_scale: f64,
+    /// Amount of hits after which the length bonus grows logarithmically.
+    pub length_bonus_hits: f64,
+    /// Multiplier of the logarithmic length bonus.
+    pub length_bonus_log_scale: f64,
+}
+
+impl PpPlusConfig {
+    /// The default PP+ configuration.
+    pub const DEFAULT: Self = Self {
+        combo_scaling_rework: true,
+        effective_miss_count: true,
+        length_bonus: true,
+        miss_weight_base: 0.96,
+        csr_exponent: 0.94,
+        length_bonus_base: 0.95,
+        length_bonus_scale: 0.4,
+        length_bonus_hits: 2000.0,
+        length_bonus_log_scale: 0.5,
+    };
+
+    /// Create the default PP+ configuration.
+    pub const fn new() -> Self {
+        Self::DEFAULT
+    }
+
+    /// The length bonus for the given amount of hits.
+    pub(crate) fn calculate_length_bonus(&self, total_hits: f64) -> f64 {
+        self.length_bonus_base
+            + self.length_bonus_scale * (total_hits / self.length_bonus_hits).min(1.0)
+            + if total_hits > self.length_bonus_hits {
+                (total_hits / self.length_bonus_hits).log10() * self.length_bonus_log_scale
+            } else {
+                0.0
+            }
+    }
+}
+
+impl Default for PpPlusConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// The algorithm that osu!standard difficulty and performance is calculated
+/// with.
+///
+/// Each model has its own attributes:
+/// - [`ScoringModel::PpPlus`]: [`OsuDifficultyAttributes`] and
+///   [`OsuPerformanceAttributes`]
+/// - [`ScoringModel::Vanilla`]: [`OsuVanillaDifficultyAttributes`] and
+///   [`OsuVanillaPerformanceAttributes`]
+///
+/// The model is stored on [`Difficulty`] and honored by
+/// [`Difficulty::calculate`] as well as [`Performance::calculate`] and the
+/// curves and solvers of [`Performance`]. Mode-specific calculators always
+/// use PP+ except for [`OsuPerformance::calculate_vanilla`]. Gradual
+/// calculations and strains are PP+ only.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty, Performance};
+/// use rosu_pp::any::PerformanceAttributes;
+/// use rosu_pp::osu::ScoringModel;
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let attrs = Performance::new(&map)
+///     .scoring_model(ScoringModel::Vanilla)
+///     .calculate();
+///
+/// assert!(matches!(attrs, PerformanceAttributes::OsuVanilla(_)));
+/// ```
+///
+/// [`OsuDifficultyAttributes`]: crate::osu::OsuDifficultyAttributes
+/// [`OsuPerformanceAttributes`]: crate::osu::OsuPerformanceAttributes
+/// [`OsuVanillaDifficultyAttributes`]: crate::osu::OsuVanillaDifficultyAttributes
+/// [`OsuVanillaPerformanceAttributes`]: crate::osu::OsuVanillaPerformanceAttributes
+/// [`Difficulty`]: crate::Difficulty
+/// [`Difficulty::calculate`]: crate::Difficulty::calculate
+/// [`Performance::calculate`]: crate::Performance::calculate
+/// [`Performance`]: crate::Performance
+/// [`OsuPerformance::calculate_vanilla`]: crate::osu::OsuPerformance::calculate_vanilla
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ScoringModel {
+    /// The PP+ model with jump, flow, precision, speed, stamina, and
+    /// accuracy skills.
+    #[default]
+    PpPlus,
+    /// The official osu!lazer model with aim, speed, and flashlight skills.
+    Vanilla,
+}
@@ -1,14 +1,17 @@
-use std::{cmp, mem};
+use std::{cmp, mem, num::NonZeroUsize};
 
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    any::difficulty::skills::StrainSkill,
-    model::mode::ConvertError,
+    model::{
+        mode::ConvertError,
+        snapshot::{self, SnapshotError, SnapshotReader, SnapshotState, SnapshotWriter},
+    },
     osu::{
         convert::convert_objects,
         object::{OsuObject, OsuObjectKind},
     },
+    util::object_times::ObjectTimes,
     Beatmap, Difficulty,
 };
 
@@ -26,6 +29,8 @@ use super::{
 /// be processed and the [`OsuDifficultyAttributes`] will be updated and
 /// returned.
 ///
+/// To move backwards, use [`OsuGradualDifficulty::seek`].
+///
 /// If you want to calculate performance attributes, use
 /// [`OsuGradualPerformance`] instead.
 ///
@@ -56,7 +61,11 @@ pub struct OsuGradualDifficulty {
     pub(crate) idx: usize,
     pub(crate) difficulty: Difficulty,
     attrs: OsuDifficultyAttributes,
+    times: ObjectTimes,
     skills: OsuSkills,
+    checkpoints: Checkpoints,
+    checksum: u64,
+    final_attrs: Option<OsuDifficultyAttributes>,
     // Lifetimes actually depend on `osu_objects` so this type is
     // self-referential. This field must be treated with great caution, moving
     // `osu_objects` will immediately invalidate `diff_objects`.
@@ -72,6 +81,7 @@ struct NotClonable;
 impl OsuGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!standard maps.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let checksum = snapshot::checksum(map, &difficulty);
         let mods = difficulty.get_mods();
         let map = map.convert_ref(GameMode::Osu, mods)?;
 
@@ -101,6 +111,11 @@ impl OsuGradualDifficulty {
             Self::increment_combo(h, &mut attrs);
         }
 
+        let times = ObjectTimes::new(
+            osu_objects.iter().map(|h| h.start_time),
+            difficulty.get_clock_rate(),
+        );
+
         let mut osu_objects = OsuObjects::new(osu_objects);
 
         let diff_objects = DifficultyValues::create_difficulty_objects(
@@ -112,18 +127,177 @@ impl OsuGradualDifficulty {
 
         let skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt, difficulty.get_lazer());
         let diff_objects = extend_lifetime(diff_objects.into_boxed_slice());
+        let checkpoints = Checkpoints::new(&attrs, &skills);
 
         Ok(Self {
             idx: 0,
             difficulty,
             attrs,
+            times,
             skills,
+            checkpoints,
+            checksum,
+            final_attrs: None,
             diff_objects,
             osu_objects,
             _not_clonable: NotClonable,
         })
     }
 
+    /// The rhythm bonus of the most recently processed hit object.
+    ///
+    /// This is the object's contribution to the accuracy rating. Objects that
+    /// are not considered for accuracy have a bonus of `0.0`.
+    pub const fn rhythm_bonus(&self) -> f64 {
+        self.skills.rhythm_complexity.last_rhythm_bonus
+    }
+
+    /// Index of the first hit object that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// The time is adjusted by the clock rate, e.g. with DT a hit object at
+    /// 3000ms has started after 2000ms of playback.
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.times.passed_count(time)
+    }
+
+    /// Process all remaining hit objects that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed hit objects.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        let n = self.idx_at_time(time).saturating_sub(self.idx);
+
+        if n > 0 {
+            self.nth(n - 1);
+        }
+
+        n
+    }
+
+    /// Process all remaining hit objects that have started at the given playback
+    /// time in milliseconds and return the resulting attributes.
+    ///
+    /// Returns `None` if no hit object has started yet.
+    pub fn attributes_at_time(&mut self, time: f64) -> Option<OsuDifficultyAttributes> {
+        match self.idx_at_time(time).saturating_sub(self.idx) {
+            0 => self.current(),
+            n => self.nth(n - 1),
+        }
+    }
+
+    /// Store a snapshot of the processing state every `interval` hit objects.
+    ///
+    /// Snapshots allow [`OsuGradualDifficulty::seek`] to move backwards
+    /// without processing all previous hit objects again at the cost of
+    /// additional memory. An `interval` of `0` disables snapshots.
+    #[must_use]
+    pub const fn checkpoint_interval(mut self, interval: usize) -> Self {
+        self.checkpoints.interval = NonZeroUsize::new(interval);
+
+        self
+    }
+
+    /// Move to the state after processing the first `idx` hit objects and
+    /// return the resulting attributes.
+    ///
+    /// Moving backwards restores the closest previous snapshot and processes
+    /// the remaining hit objects from there, see
+    /// [`OsuGradualDifficulty::checkpoint_interval`].
+    ///
+    /// Returns `None` if `idx` is `0` or exceeds the amount of hit objects.
+    pub fn seek(&mut self, idx: usize) -> Option<OsuDifficultyAttributes> {
+        if idx > self.idx + self.len() {
+            return None;
+        }
+
+        if idx < self.idx {
+            let checkpoint = self.checkpoints.closest(idx);
+
+            self.idx = checkpoint.idx;
+            self.attrs.clone_from(&checkpoint.attrs);
+            self.skills.clone_from(&checkpoint.skills);
+        }
+
+        match idx - self.idx {
+            0 => self.current(),
+            n => self.nth(n - 1),
+        }
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// The snapshot contains the amount of processed hit objects, the skills'
+    /// strains, and the attributes so far. Checkpoints are not included.
+    ///
+    /// Use [`OsuGradualDifficulty::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        snapshot::to_snapshot(self, GameMode::Osu, self.checksum)
+    }
+
+    /// Restore a snapshot of [`OsuGradualDifficulty::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let mut gradual = Self::new(difficulty, map)?;
+        let checksum = gradual.checksum;
+        snapshot::from_snapshot(&mut gradual, GameMode::Osu, checksum, snapshot)?;
+
+        Ok(gradual)
+    }
+
+    pub(crate) const fn checksum(&self) -> u64 {
+        self.checksum
+    }
+
+    /// The attributes after processing all hit objects.
+    ///
+    /// The processing state remains unchanged and the result is cached.
+    pub(crate) fn final_attributes(&mut self) -> Option<OsuDifficultyAttributes> {
+        if self.final_attrs.is_none() {
+            let idx = self.idx;
+            let attrs = self.attrs.clone();
+            let skills = self.skills.clone();
+
+            self.final_attrs = match self.len() {
+                0 => self.current(),
+                _ => self.nth(usize::MAX),
+            };
+
+            self.idx = idx;
+            self.attrs = attrs;
+            self.skills = skills;
+        }
+
+        self.final_attrs.clone()
+    }
+
+    /// The most recently processed hit object.
+    pub(crate) fn last_object(&self) -> Option<&OsuObject> {
+        self.idx
+            .checked_sub(1)
+            .and_then(|idx| self.osu_objects.get(idx))
+    }
+
+    /// The attributes of all hit objects that have been processed so far.
+    pub(crate) fn current(&self) -> Option<OsuDifficultyAttributes> {
+        if self.idx == 0 {
+            return None;
+        }
+
+        let mut attrs = self.attrs.clone();
+
+        DifficultyValues::eval(&mut attrs, self.difficulty.get_mods(), &self.skills);
+
+        Some(attrs)
+    }
+
     fn increment_combo(h: &OsuObject, attrs: &mut OsuDifficultyAttributes) {
         attrs.max_combo += 1;
 
@@ -139,6 +313,25 @@ impl OsuGradualDifficulty {
     }
 }
 
+impl SnapshotState for OsuGradualDifficulty {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.idx.write(writer);
+        self.attrs.write(writer);
+        self.skills.write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        self.idx.read(reader)?;
+
+        if self.idx > self.osu_objects.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+
+        self.attrs.read(reader)?;
+        self.skills.read(reader)
+    }
+}
+
 fn extend_lifetime(
     diff_objects: Box<[OsuDifficultyObject<'_>]>,
 ) -> Box<[OsuDifficultyObject<'static>]> {
@@ -159,13 +352,7 @@ impl Iterator for OsuGradualDifficulty {
         if self.idx > 0 {
             let curr = self.diff_objects.get(self.idx - 1)?;
 
-            self.skills.aim.process(curr, &self.diff_objects);
-            self.skills.raw_aim.process(curr, &self.diff_objects);
-            self.skills.jump_aim.process(curr, &self.diff_objects);
-            self.skills.flow_aim.process(curr, &self.diff_objects);
-            self.skills.speed.process(curr, &self.diff_objects);
-            self.skills.stamina.process(curr, &self.diff_objects);
-            self.skills.rhythm_complexity.process(curr, &self.diff_objects);
+            self.skills.process(curr, &self.diff_objects);
 
             Self::increment_combo(curr.base, &mut self.attrs);
         } else if self.osu_objects.is_empty() {
@@ -173,12 +360,9 @@ impl Iterator for OsuGradualDifficulty {
         }
 
         self.idx += 1;
+        self.checkpoints.save(self.idx, &self.attrs, &self.skills);
 
-        let mut attrs = self.attrs.clone();
-
-        DifficultyValues::eval(&mut attrs, self.difficulty.get_mods(), &self.skills);
-
-        Some(attrs)
+        self.current()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -196,12 +380,14 @@ impl Iterator for OsuGradualDifficulty {
         if self.idx == 0 && take > 0 {
             take -= 1;
             self.idx += 1;
+            self.checkpoints.save(self.idx, &self.attrs, &self.skills);
         }
 
         for curr in skip_iter.take(take) {
             self.skills.process(curr, &self.diff_objects);
             Self::increment_combo(curr.base, &mut self.attrs);
             self.idx += 1;
+            self.checkpoints.save(self.idx, &self.attrs, &self.skills);
         }
 
         self.next()
@@ -214,6 +400,61 @@ impl ExactSizeIterator for OsuGradualDifficulty {
     }
 }
 
+/// Snapshots of the processing state for [`OsuGradualDifficulty::seek`].
+struct Checkpoints {
+    interval: Option<NonZeroUsize>,
+    /// Ordered by `idx`; the first snapshot is always the initial state.
+    snapshots: Vec<Checkpoint>,
+}
+
+struct Checkpoint {
+    idx: usize,
+    attrs: OsuDifficultyAttributes,
+    skills: OsuSkills,
+}
+
+impl Checkpoints {
+    fn new(attrs: &OsuDifficultyAttributes, skills: &OsuSkills) -> Self {
+        let initial = Checkpoint {
+            idx: 0,
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+        };
+
+        Self {
+            interval: None,
+            snapshots: vec![initial],
+        }
+    }
+
+    fn save(&mut self, idx: usize, attrs: &OsuDifficultyAttributes, skills: &OsuSkills) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+
+        // Snapshots beyond `idx` already exist after seeking backwards
+        if !idx.is_multiple_of(interval.get())
+            || self.snapshots.last().is_some_and(|c| c.idx >= idx)
+        {
+            return;
+        }
+
+        self.snapshots.push(Checkpoint {
+            idx,
+            attrs: attrs.clone(),
+            skills: skills.clone(),
+        });
+    }
+
+    /// The last snapshot at or before `idx`.
+    fn closest(&self, idx: usize) -> &Checkpoint {
+        let i = self.snapshots.partition_point(|c| c.idx <= idx);
+
+        // The initial snapshot has index 0 so `i` is at least 1
+        &self.snapshots[i - 1]
+    }
+}
+
 mod osu_objects {
     use std::pin::Pin;
 
@@ -229,10 +470,18 @@ mod osu_objects {
             Self { objects }
         }
 
+        pub(super) const fn len(&self) -> usize {
+            self.objects.len()
+        }
+
         pub(super) const fn is_empty(&self) -> bool {
             self.objects.is_empty()
         }
 
+        pub(super) fn get(&self, idx: usize) -> Option<&OsuObject> {
+            self.objects.get(idx)
+        }
+
         pub(super) fn iter_mut(&mut self) -> impl ExactSizeIterator<Item = Pin<&mut OsuObject>> {
             self.objects.iter_mut().map(Pin::new)
         }
@@ -291,4 +540,110 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn rhythm_bonus() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut gradual = OsuGradualDifficulty::new(Difficulty::new(), &map).unwrap();
+        let mut bonuses = Vec::with_capacity(map.hit_objects.len());
+
+        while gradual.next().is_some() {
+            bonuses.push(gradual.rhythm_bonus());
+        }
+
+        assert_eq!(bonuses.len(), map.hit_objects.len());
+        assert!(bonuses[0].abs() < f64::EPSILON);
+        assert!(bonuses.iter().all(|&bonus| bonus >= 0.0));
+        assert!(bonuses.iter().any(|&bonus| bonus > 0.0));
+
+        let strains = Difficulty::new().strains_for_mode::<Osu>(&map).unwrap();
+        assert_eq!(strains.accuracy.len(), strains.aim.len());
+        assert!(strains.accuracy.iter().any(|&strain| strain > 0.0));
+    }
+
+    #[test]
+    fn time() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = OsuGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        // The first hit object starts at 2810ms i.e. at 1873.3ms with DT
+        assert_eq!(gradual.idx_at_time(1873.0), 0);
+        assert_eq!(gradual.idx_at_time(1874.0), 1);
+        assert!(gradual.attributes_at_time(1000.0).is_none());
+
+        for time in [20_000.0, 20_000.0, 50_000.0, f64::MAX] {
+            let idx = gradual.idx_at_time(time);
+            let attrs = gradual.attributes_at_time(time).unwrap();
+
+            let expected = difficulty
+                .clone()
+                .passed_objects(idx as u32)
+                .calculate_for_mode::<Osu>(&map)
+                .unwrap();
+
+            assert_eq!(gradual.idx, idx);
+            assert_eq!(attrs, expected);
+        }
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.advance_to_time(f64::MAX), 0);
+    }
+
+    #[test]
+    fn seek() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(8); // HD
+
+        let with_checkpoints = OsuGradualDifficulty::new(difficulty.clone(), &map)
+            .unwrap()
+            .checkpoint_interval(64);
+        let without_checkpoints = OsuGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        let n_objects = map.hit_objects.len();
+
+        for mut gradual in [with_checkpoints, without_checkpoints] {
+            for idx in [300, 50, 64, 65, 1, n_objects, 128, 127] {
+                let attrs = gradual.seek(idx).unwrap();
+
+                let expected = difficulty
+                    .clone()
+                    .passed_objects(idx as u32)
+                    .calculate_for_mode::<Osu>(&map)
+                    .unwrap();
+
+                assert_eq!(gradual.idx, idx);
+                assert_eq!(attrs, expected);
+            }
+
+            assert!(gradual.seek(0).is_none());
+            assert!(gradual.seek(n_objects + 1).is_none());
+            assert_eq!(gradual.idx, 0);
+        }
+    }
+
+    #[test]
+    fn snapshot() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(8); // HD
+        let mut gradual = OsuGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+        gradual.nth(299);
+
+        let snapshot = gradual.to_snapshot();
+        let restored =
+            OsuGradualDifficulty::from_snapshot(difficulty.clone(), &map, &snapshot).unwrap();
+
+        assert_eq!(restored.idx, gradual.idx);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(restored.last(), gradual.last());
+
+        assert!(matches!(
+            OsuGradualDifficulty::from_snapshot(Difficulty::new(), &map, &snapshot),
+            Err(SnapshotError::ChecksumMismatch)
+        ));
+    }
 }
@@ -20,14 +20,14 @@ use self::skills::OsuSkills;
 use super::attributes::OsuDifficultyAttributes;
 
 pub mod gradual;
-mod object;
+pub mod object;
 pub mod scaling_factor;
 pub mod skills;
 
 const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
 
-const HD_FADE_IN_DURATION_MULTIPLIER: f64 = 0.4;
-const HD_FADE_OUT_DURATION_MULTIPLIER: f64 = 0.3;
+pub const HD_FADE_IN_DURATION_MULTIPLIER: f64 = 0.4;
+pub const HD_FADE_OUT_DURATION_MULTIPLIER: f64 = 0.3;
 
 pub fn difficulty(
     difficulty: &Difficulty,
@@ -84,6 +84,15 @@ pub struct DifficultyValues {
 
 impl DifficultyValues {
     pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        Self::calculate_with(difficulty, map, |_, _| {})
+    }
+
+    /// Same as [`DifficultyValues::calculate`] but `on_object` is called after
+    /// each processed difficulty object.
+    pub fn calculate_with<F>(difficulty: &Difficulty, map: &Beatmap, mut on_object: F) -> Self
+    where
+        F: FnMut(&OsuDifficultyObject<'_>, &OsuSkills),
+    {
         let mods = difficulty.get_mods();
         let take = difficulty.get_passed_objects();
 
@@ -115,6 +124,7 @@ impl DifficultyValues {
 
         for hit_object in diff_objects.iter().take(take_diff_objects) {
             skills.process(hit_object, &diff_objects);
+            on_object(hit_object, &skills);
         }
 
         Self { skills, attrs }
@@ -123,48 +133,69 @@ impl DifficultyValues {
     /// Process the difficulty values and store the results in `attrs`.
     pub fn eval(attrs: &mut OsuDifficultyAttributes, mods: &GameMods, skills: &OsuSkills) {
         let OsuSkills {
-            aim,
-            raw_aim,
-            jump_aim,
-            flow_aim,
+            aim:
+                aim_skill @ Aim {
+                    all: aim,
+                    raw: raw_aim,
+                    jump: jump_aim,
+                    flow: flow_aim,
+                    precision,
+                    reading,
+                    ..
+                },
             speed,
             stamina,
             rhythm_complexity,
+            flashlight,
         } = skills;
         let aim_difficulty_value = aim.cloned_difficulty_value();
         let raw_aim_difficulty_value = raw_aim.cloned_difficulty_value();
         let jump_aim_difficulty_value = jump_aim.cloned_difficulty_value();
         let flow_aim_difficulty_value = flow_aim.cloned_difficulty_value();
+        let precision_difficulty_value = precision.cloned_difficulty_value();
+        let reading_difficulty_value = reading.cloned_difficulty_value();
         let speed_difficulty_value = speed.cloned_difficulty_value();
         let stamina_difficulty_value = stamina.cloned_difficulty_value();
         let rhythm_difficulty_value = rhythm_complexity.cloned_difficulty_value();
+        let flashlight_difficulty_value = flashlight.cloned_difficulty_value();
 
         let mut aim_rating = aim_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
         let jump_aim_rating = jump_aim_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
         let flow_aim_rating = flow_aim_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
-        let precision_rating = (aim_difficulty_value - raw_aim_difficulty_value).max(0.0).sqrt() * DIFFICULTY_MULTIPLIER;
+        let precision_rating = precision_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
+        let mut reading_rating = reading_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
         let mut speed_rating = speed_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
         let stamina_rating = stamina_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
         let accuracy_rating = rhythm_difficulty_value.sqrt();
+        let mut flashlight_rating = flashlight_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
 
 
         let aim_difficult_strain_count = aim.count_top_weighted_strains(aim_difficulty_value);
         let jump_aim_difficult_strain_count = jump_aim.count_top_weighted_strains(raw_aim_difficulty_value);
         let flow_aim_difficult_strain_count = flow_aim.count_top_weighted_strains(flow_aim_difficulty_value);
+        let precision_difficult_strain_count = precision.count_top_weighted_strains(precision_difficulty_value);
         let speed_difficult_strain_count = speed.count_top_weighted_strains(speed_difficulty_value);
         let stamina_difficult_strain_count = stamina.count_top_weighted_strains(stamina_difficulty_value);
-        let difficult_sliders = aim.get_difficult_sliders();
+        let flashlight_difficult_strain_count =
+            flashlight.count_top_weighted_strains(flashlight_difficulty_value);
+        let difficult_sliders = aim_skill.get_difficult_sliders();
 
         if mods.td() {
             aim_rating = aim_rating.powf(0.8);
+            reading_rating = reading_rating.powf(0.8);
+            flashlight_rating = flashlight_rating.powf(0.8);
         }
 
         if mods.rx() {
             aim_rating *= 0.9;
+            reading_rating *= 0.9;
             speed_rating = 0.0;
+            flashlight_rating *= 0.7;
         } else if mods.ap() { // 这个pp+没有，这边保留osu原装代码
             speed_rating *= 0.5;
             aim_rating = 0.0;
+            reading_rating = 0.0;
+            flashlight_rating *= 0.4;
         }
 
         // sr计算改到下面来
@@ -178,14 +209,28 @@ impl DifficultyValues {
         attrs.jump = jump_aim_rating;
         attrs.flow = flow_aim_rating;
         attrs.precision = precision_rating;
+        attrs.reading = reading_rating;
         attrs.speed = speed_rating;
         attrs.stamina = stamina_rating;
         attrs.accuracy = accuracy_rating;
+        attrs.flashlight = flashlight_rating;
         attrs.aim_difficult_strain_count = aim_difficult_strain_count;
         attrs.jump_aim_difficult_strain_count = jump_aim_difficult_strain_count;
         attrs.flow_aim_difficult_strain_count = flow_aim_difficult_strain_count;
+        attrs.precision_difficult_strain_count = precision_difficult_strain_count;
         attrs.speed_difficult_strain_count = speed_difficult_strain_count;
         attrs.stamina_difficult_strain_count = stamina_difficult_strain_count;
+        attrs.flashlight_difficult_strain_count = flashlight_difficult_strain_count;
+        attrs.flow_ratio = style_ratio(rhythm_complexity.flow_total, rhythm_complexity.object_count);
+        attrs.mean_jump_dist = style_ratio(rhythm_complexity.jump_total, rhythm_complexity.object_count);
+        attrs.stream_share = style_ratio(
+            f64::from(rhythm_complexity.stream_count),
+            rhythm_complexity.object_count,
+        );
+        attrs.jump_share = style_ratio(
+            f64::from(rhythm_complexity.jump_count),
+            rhythm_complexity.object_count,
+        );
         attrs.stars = star_rating;
     }
 
@@ -211,6 +256,7 @@ impl DifficultyValues {
         let mut last_last = None;
         let mut last_diff_object: Option<&OsuDifficultyObject> = None;
         let mut last_last_diff_object: Option<&OsuDifficultyObject> = None;
+        let mut combo = last.max_combo();
 
         let mut diff_objects: Vec<OsuDifficultyObject<'a>> = osu_objects_iter
             .enumerate()
@@ -235,6 +281,9 @@ impl DifficultyValues {
                 scaling_factor,
             );
 
+            diff_object.combo = combo;
+            combo += diff_object.base.max_combo();
+
             last_last_diff_object = last_diff_object;
             last_diff_object = Some(diff_object);
 
@@ -245,3 +294,11 @@ impl DifficultyValues {
         diff_objects
     }
 }
+
+fn style_ratio(total: f64, object_count: u32) -> f64 {
+    if object_count == 0 {
+        0.0
+    } else {
+        total / f64::from(object_count)
+    }
+}
@@ -28,6 +28,8 @@ pub struct OsuDifficultyObject<'a> {
     pub angle: Option<f64>,
     pub angle_leniency: f64,
     pub preempt: f64,
+    /// Combo right before this object, assuming no previous object was missed.
+    pub combo: u32,
     stream_bpm: f64,
 }
 
@@ -61,6 +63,7 @@ impl<'a> OsuDifficultyObject<'a> {
             angle: None,
             angle_leniency: 0.0,
             preempt: 0.0,
+            combo: 0,
             stream_bpm: 0.0,
         };
 
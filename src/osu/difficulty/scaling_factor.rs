@@ -19,11 +19,17 @@ pub struct ScalingFactor {
 
 impl ScalingFactor {
     pub fn new(cs: f64) -> Self {
+        Self::with_normalized_radius(cs, OsuDifficultyObject::NORMALIZED_RADIUS as f32)
+    }
+
+    /// Same as [`ScalingFactor::new`] but distances are scaled to circles of
+    /// radius `normalized_radius` instead of PP+'s radius.
+    pub fn with_normalized_radius(cs: f64, normalized_radius: f32) -> Self {
         let scale = (f64::from(1.0_f32) - f64::from(0.7_f32) * ((cs - 5.0) / 5.0)) as f32 / 2.0
             * BROKEN_GAMEFIELD_ROUNDING_ALLOWANCE;
 
         let radius = f64::from(OsuObject::OBJECT_RADIUS * scale);
-        let factor = OsuDifficultyObject::NORMALIZED_RADIUS as f32 / radius as f32;
+        let factor = normalized_radius / radius as f32;
 
         let factor_with_small_circle_bonus = if radius < 30.0 {
             factor * (1.0 + (30.0 - radius as f32).min(5.0) / 50.0)
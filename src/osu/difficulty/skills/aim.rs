@@ -15,31 +15,145 @@ use crate::{
 
 use super::strain::OsuStrainSkill;
 
-#[derive(Clone, Copy)]
-pub enum AimType {
-    All,
-    Flow,
-    Jump,
-    Raw,
+/// Evaluates all aim variants at once and feeds their values into separate
+/// strain accumulators.
+///
+/// Jump, flow, small circle, and reading values only depend on the current
+/// object so they are computed a single time per object.
+#[derive(Clone)]
+pub struct Aim {
+    /// Jump and flow aim including the small circle bonus.
+    pub all: AimStrain,
+    /// Jump and flow aim without the small circle bonus.
+    pub raw: AimStrain,
+    /// Only jump aim.
+    pub jump: AimStrain,
+    /// Only flow aim.
+    pub flow: AimStrain,
+    /// The portion of jump and flow aim that stems from the small circle
+    /// bonus, weighted by how precisely each object has to be hit.
+    pub precision: AimStrain,
+    /// The low approach rate bonus on top of jump and flow aim.
+    ///
+    /// Not included in any of the other variants.
+    pub reading: AimStrain,
+    radius: f64,
+    has_hidden: bool,
+    has_fl: bool,
+    time_preempt: f64,
+    time_fade_in: f64,
+    slider_strains: Vec<f64>, // TODO: use `StrainsVec`?
+    evaluator: AimEvaluator,
 }
 
-define_skill! {
-    #[derive(Clone)]
-    pub struct Aim: StrainSkill => [OsuDifficultyObject<'a>][OsuDifficultyObject<'a>] {
+snapshot_state!(Aim {
+    all,
+    raw,
+    jump,
+    flow,
+    precision,
+    reading,
+    slider_strains,
+    evaluator,
+});
+
+impl Aim {
+    pub fn new(
         radius: f64,
         has_hidden: bool,
         has_fl: bool,
-        aim_type: AimType,
+        time_preempt: f64,
+        time_fade_in: f64,
+    ) -> Self {
+        Self {
+            all: AimStrain::new(),
+            raw: AimStrain::new(),
+            jump: AimStrain::new(),
+            flow: AimStrain::new(),
+            precision: AimStrain::new(),
+            reading: AimStrain::new(),
+            radius,
+            has_hidden,
+            has_fl,
+            time_preempt,
+            time_fade_in,
+            slider_strains: Vec::with_capacity(64),
+            evaluator: AimEvaluator::new(),
+        }
+    }
+
+    pub fn process(&mut self, curr: &OsuDifficultyObject<'_>, objects: &[OsuDifficultyObject<'_>]) {
+        let AimValues {
+            all,
+            raw,
+            jump,
+            flow,
+            precision,
+            reading,
+        } = self.evaluator.evaluate_diff_of(
+            curr,
+            objects,
+            self.radius,
+            self.has_hidden,
+            self.has_fl,
+            self.time_preempt,
+            self.time_fade_in,
+        );
+
+        self.all.process_difficulty(all, curr, objects);
+        self.raw.process_difficulty(raw, curr, objects);
+        self.jump.process_difficulty(jump, curr, objects);
+        self.flow.process_difficulty(flow, curr, objects);
+        self.precision.process_difficulty(precision, curr, objects);
+        self.reading.process_difficulty(reading, curr, objects);
+
+        if curr.base.is_slider() {
+            self.slider_strains.push(self.all.current_strain);
+        }
+    }
+
+    pub fn get_difficult_sliders(&self) -> f64 {
+        if self.slider_strains.is_empty() {
+            return 0.0;
+        }
+
+        let max_slider_strain = self.slider_strains.iter().copied().fold(0.0, f64::max);
+
+        if FloatExt::eq(max_slider_strain, 0.0) {
+            return 0.0;
+        }
+
+        self.slider_strains
+            .iter()
+            .copied()
+            .map(|strain| 1.0 / (1.0 + f64::exp(-(strain / max_slider_strain * 12.0 - 6.0))))
+            .sum()
+    }
+}
+
+define_skill! {
+    #[derive(Clone)]
+    pub struct AimStrain: StrainSkill => [OsuDifficultyObject<'a>][OsuDifficultyObject<'a>] {
         current_strain: f64 = 0.0,
-        slider_strains: Vec<f64> = Vec::with_capacity(64), // TODO: use `StrainsVec`?
-        evaluator: AimEvaluator = AimEvaluator::new(),
+        curr_difficulty: f64 = 0.0,
     }
 }
 
-impl Aim {
+impl AimStrain {
     const SKILL_MULTIPLIER: f64 = 1059.0;
     const STRAIN_DECAY_BASE: f64 = 0.15;
 
+    /// Process the current object with its already evaluated difficulty.
+    fn process_difficulty(
+        &mut self,
+        difficulty: f64,
+        curr: &OsuDifficultyObject<'_>,
+        objects: &[OsuDifficultyObject<'_>],
+    ) {
+        self.curr_difficulty = difficulty;
+        <Self as StrainSkill>::process(self, curr, objects);
+    }
+
     fn calculate_initial_strain(
         &mut self,
         time: f64,
@@ -56,45 +170,40 @@ impl Aim {
     fn strain_value_at(
         &mut self,
         curr: &OsuDifficultyObject<'_>,
-        objects: &[OsuDifficultyObject<'_>],
+        _objects: &[OsuDifficultyObject<'_>],
     ) -> f64 {
         self.current_strain *= strain_decay(curr.delta_time, Self::STRAIN_DECAY_BASE);
-        self.current_strain += self.evaluator.evaluate_diff_of(curr, objects, self.radius, self.has_hidden, self.has_fl, self.aim_type)
-            * Self::SKILL_MULTIPLIER;
-
-        if curr.base.is_slider() {
-            self.slider_strains.push(self.current_strain);
-        }
+        self.current_strain += self.curr_difficulty * Self::SKILL_MULTIPLIER;
 
         self.current_strain
     }
+}
 
-    pub fn get_difficult_sliders(&self) -> f64 {
-        if self.slider_strains.is_empty() {
-            return 0.0;
-        }
-
-        let max_slider_strain = self.slider_strains.iter().copied().fold(0.0, f64::max);
-
-        if FloatExt::eq(max_slider_strain, 0.0) {
-            return 0.0;
-        }
+impl OsuStrainSkill for AimStrain {}
 
-        self.slider_strains
-            .iter()
-            .copied()
-            .map(|strain| 1.0 / (1.0 + f64::exp(-(strain / max_slider_strain * 12.0 - 6.0))))
-            .sum()
-    }
+/// Aim values of a single object for all aim variants.
+struct AimValues {
+    all: f64,
+    raw: f64,
+    jump: f64,
+    flow: f64,
+    precision: f64,
+    reading: f64,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Default)]
 pub struct PreemptOsuObject {
     pub start_time: f64,
     pub jump_dist: f64,
     pub base_flow: f64,
 }
 
+snapshot_state!(PreemptOsuObject {
+    start_time,
+    jump_dist,
+    base_flow,
+});
+
 impl From<&OsuDifficultyObject<'_>> for PreemptOsuObject {
     fn from(obj: &OsuDifficultyObject<'_>) -> Self {
         Self {
@@ -105,14 +214,19 @@ impl From<&OsuDifficultyObject<'_>> for PreemptOsuObject {
     }
 }
 
-impl OsuStrainSkill for Aim {}
-
 #[derive(Clone)]
 struct AimEvaluator {
     preempt_hit_objects: VecDeque<PreemptOsuObject>
 }
 
+snapshot_state!(AimEvaluator {
+    preempt_hit_objects,
+});
+
 impl AimEvaluator {
+    const HIDDEN_BONUS_SCALE: f64 = 2.2;
+    const LOW_AR_BONUS: f64 = 0.15;
+
     const fn new() -> Self {
         Self {
             preempt_hit_objects: VecDeque::new(),
@@ -120,7 +234,7 @@ impl AimEvaluator {
     }
 
 
-    #[allow(clippy::too_many_lines)]
+    #[allow(clippy::too_many_arguments)]
     fn evaluate_diff_of<'a>(
         &mut self,
         curr: &'a OsuDifficultyObject<'a>,
@@ -128,35 +242,44 @@ impl AimEvaluator {
         radius: f64,
         has_hidden: bool,
         has_fl: bool,
-        aim_type: AimType,
-    ) -> f64 {
+        time_preempt: f64,
+        time_fade_in: f64,
+    ) -> AimValues {
         let osu_curr_obj = curr;
 
         let prev2s: [Option<&OsuDifficultyObject>; 2] = [curr.previous(0, diff_objects), curr.previous(1, diff_objects)];
 
-        let aim = match aim_type {
-            AimType::All => {
-                let jump_aim = Self::calc_jump_aim_value(osu_curr_obj, &prev2s);
-                let flow_aim = Self::calc_flow_aim_value(osu_curr_obj, prev2s[0]);
-                let small_circle_bonus = Self::calc_small_circle_bonus(radius);
-                (jump_aim + flow_aim) * small_circle_bonus
-            },
-            AimType::Flow => Self::calc_flow_aim_value(osu_curr_obj, prev2s[0]) * Self::calc_small_circle_bonus(radius),
-            AimType::Jump => Self::calc_jump_aim_value(osu_curr_obj, &prev2s) * Self::calc_small_circle_bonus(radius),
-            AimType::Raw => Self::calc_flow_aim_value(osu_curr_obj, prev2s[0]) + Self::calc_jump_aim_value(osu_curr_obj, &prev2s)
-        };
-        
-        let reading_multiplier = self.calc_reading_multiplier(
+        let jump_aim = Self::calc_jump_aim_value(osu_curr_obj, &prev2s);
+        let flow_aim = Self::calc_flow_aim_value(osu_curr_obj, prev2s[0]);
+        let small_circle_bonus = Self::calc_small_circle_bonus(radius);
+
+        let (reading_multiplier, low_ar_multiplier) = self.calc_reading_multiplier(
             osu_curr_obj,
+            diff_objects,
             has_hidden,
             has_fl,
             radius,
+            time_preempt,
+            time_fade_in,
         );
 
-        aim * reading_multiplier
+        // The low approach rate bonus is only part of the reading strain
+        AimValues {
+            all: (jump_aim + flow_aim) * small_circle_bonus * reading_multiplier,
+            raw: (flow_aim + jump_aim) * reading_multiplier,
+            jump: jump_aim * small_circle_bonus * reading_multiplier,
+            flow: flow_aim * small_circle_bonus * reading_multiplier,
+            precision: (jump_aim + flow_aim)
+                * (small_circle_bonus - 1.0)
+                * Self::calc_precision_weight(osu_curr_obj, radius)
+                * reading_multiplier,
+            reading: (jump_aim + flow_aim)
+                * small_circle_bonus
+                * reading_multiplier
+                * (low_ar_multiplier - 1.0),
+        }
     }
 
-    
     fn calc_jump_aim_value(
         curr: &OsuDifficultyObject,
         prev2s: &[Option<&OsuDifficultyObject>; 2],
@@ -219,13 +342,19 @@ impl AimEvaluator {
         flow_aim * curr.flow
     }
 
+    /// Returns the reading multiplier without the low approach rate bonus and
+    /// the low approach rate multiplier separately.
+    #[allow(clippy::too_many_arguments)]
     fn calc_reading_multiplier<'a>(
         &mut self,
         curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
         has_hidden: bool,
         has_fl: bool,
         radius: f64,
-    ) -> f64 {
+        time_preempt: f64,
+        time_fade_in: f64,
+    ) -> (f64, f64) {
         while !self.preempt_hit_objects.is_empty()
             && self.preempt_hit_objects.front().unwrap().start_time < curr.start_time - curr.preempt
         {
@@ -240,19 +369,29 @@ impl AimEvaluator {
         // ~10-15% relative aim bonus at higher density values.
         let density_bonus = reading_strain.powf(1.5) / 100.0;
 
-        let reading_multiplier = if has_hidden {
-            1.05 + density_bonus * 1.5 // 5% flat aim bonus and density bonus increased by 50%.
+        let hidden_bonus = if has_hidden {
+            let hidden_strain =
+                Self::calc_hidden_strain(curr, diff_objects, time_preempt, time_fade_in);
+
+            Self::HIDDEN_BONUS_SCALE * hidden_strain.powf(1.5) / 100.0
         } else {
-            1.0 + density_bonus
+            0.0
         };
 
+        let reading_multiplier = 1.0 + density_bonus + hidden_bonus;
+
         let flashlight_multiplier =
             Self::calc_flashlight_multiplier(has_fl, curr.raw_jump_dist, radius);
         let high_approach_rate_multiplier = Self::calc_high_ar_multiplier(curr.preempt);
+        let low_approach_rate_multiplier =
+            Self::calc_low_ar_multiplier(curr.preempt, self.preempt_hit_objects.len());
 
         self.preempt_hit_objects.push_back(PreemptOsuObject::from(curr));
 
-        reading_multiplier * flashlight_multiplier * high_approach_rate_multiplier
+        (
+            reading_multiplier * flashlight_multiplier * high_approach_rate_multiplier,
+            low_approach_rate_multiplier,
+        )
     }
 
     fn calc_jump_pattern_weight(curr: &OsuDifficultyObject, prev2s: &[Option<&OsuDifficultyObject>; 2]) -> f64 {
@@ -442,6 +581,31 @@ impl AimEvaluator {
         }
     }
 
+    /// Density of the objects that started fading out with hidden while
+    /// `curr` is hit, weighted by how far they already faded out.
+    ///
+    /// The current object itself is always fully faded at that point while
+    /// objects that are still fading in don't count. Lower approach rates
+    /// keep more objects on screen so more of them are faded.
+    fn calc_hidden_strain<'a>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        time_preempt: f64,
+        time_fade_in: f64,
+    ) -> f64 {
+        let hit_time = curr.base.start_time;
+
+        std::iter::once(curr)
+            .chain((0..).map_while(|i| curr.next(i, diff_objects)))
+            .take_while(|next| next.base.start_time - time_preempt + time_fade_in <= hit_time)
+            .map(|next| {
+                let faded = 1.0 - next.opacity_at(hit_time, true, time_preempt, time_fade_in);
+
+                faded * Self::calc_reading_density(next.base_flow, next.jump_dist)
+            })
+            .sum()
+    }
+
     fn calc_reading_density(prev_base_flow: f64, prev_jump_dist: f64) -> f64 {
         (1.0 - prev_base_flow * 0.75)
             * (1.0
@@ -469,7 +633,311 @@ impl AimEvaluator {
         1.0 + 120.0 / radius.powf(2.0)
     }
 
+    /// How precisely the object has to be hit, based on the spacing relative
+    /// to the circle size and on sharp angles that require stopping on the
+    /// circle.
+    ///
+    /// The spacing part is Fitts's index of difficulty `log2(D / W + 1)` with
+    /// the circle diameter as target width `W`. Objects on top of each other
+    /// need no aiming at all and weigh `0.0`. The index is halved so that a
+    /// spacing of three diameters, a common jump spacing, weighs `1.0`; this
+    /// keeps the overall precision rating close to its value before the
+    /// weighting, e.g. 0.897 to 0.921 stars on map 2785319.
+    ///
+    /// Angles below 45° add up to 50% because the cursor has to stop and
+    /// reverse on the circle instead of passing through it. The bonus fades
+    /// out towards 90° where the cursor can keep most of its momentum.
+    fn calc_precision_weight(curr: &OsuDifficultyObject, radius: f64) -> f64 {
+        let spacing_weight = (curr.raw_jump_dist / (2.0 * radius) + 1.0).log2() / 2.0;
+
+        let angle_weight = curr.angle.map_or(1.0, |angle| {
+            1.0 + 0.5 * pplus::transition_to_false(angle, PI / 4.0, PI / 2.0)
+        });
+
+        spacing_weight * angle_weight
+    }
+
     fn calc_high_ar_multiplier(preempt: f64) -> f64 {
         1.0 + (-((preempt - 325.0) / 30.0).tanh() + 1.0) / 15.0
     }
+
+    /// Bonus for reading overlapping approach circles, starting below AR8
+    /// and maxing out at AR5 with many objects on screen.
+    fn calc_low_ar_multiplier(preempt: f64, n_on_screen: usize) -> f64 {
+        let approach_rate_factor = pplus::transition_to_true(preempt, 750.0, 450.0);
+        let density_factor = (n_on_screen as f64 / 8.0).tanh();
+
+        1.0 + Self::LOW_AR_BONUS * approach_rate_factor * density_factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use crate::{
+        any::difficulty::skills::StrainSkill,
+        model::beatmap::BeatmapAttributesBuilder,
+        osu::{
+            attributes::OsuDifficultyAttributes,
+            convert::convert_objects,
+            difficulty::{
+                object::OsuDifficultyObject, scaling_factor::ScalingFactor, DifficultyValues,
+            },
+            Osu,
+        },
+        Beatmap, Difficulty,
+    };
+
+    use super::{AimEvaluator, AimStrain};
+
+    /// Preempt of AR9 in milliseconds.
+    const PREEMPT: f64 = 600.0;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    /// A CS4 AR9 map with the given hit objects.
+    fn parse(hit_objects: &str) -> Beatmap {
+        let content = format!(
+            "osu file format v14\n\n[General]\nMode: 0\n\n[Difficulty]\n\
+            HPDrainRate:5\nCircleSize:4\nOverallDifficulty:8\nApproachRate:9\n\
+            SliderMultiplier:1.4\nSliderTickRate:1\n\n[TimingPoints]\n\
+            0,500,4,2,0,100,1,0\n\n[HitObjects]\n{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    fn with_diff_objects<R>(
+        hit_objects: &str,
+        f: impl FnOnce(&[OsuDifficultyObject<'_>]) -> R,
+    ) -> R {
+        let map = parse(hit_objects);
+        let difficulty = Difficulty::new();
+        let scaling_factor = ScalingFactor::new(4.0);
+
+        let mut osu_objects = convert_objects(
+            &map,
+            &scaling_factor,
+            difficulty.get_mods().reflection(),
+            PREEMPT,
+            usize::MAX,
+            &mut OsuDifficultyAttributes::default(),
+        );
+
+        let diff_objects = DifficultyValues::create_difficulty_objects(
+            &difficulty,
+            &scaling_factor,
+            osu_objects.iter_mut().map(Pin::new),
+            PREEMPT,
+        );
+
+        f(&diff_objects)
+    }
+
+    /// Values of the separate per-variant aim skills before they were
+    /// evaluated in a single pass.
+    #[test]
+    fn matches_separate_skills() {
+        fn check(strain: &AimStrain, value: f64, count: f64, sum: f64, max: f64) {
+            let difficulty_value = strain.cloned_difficulty_value();
+            assert_close(difficulty_value, value);
+            assert_close(strain.count_top_weighted_strains(difficulty_value), count);
+
+            let peaks = strain.clone().into_current_strain_peaks().into_vec();
+            assert_eq!(peaks.len(), 282);
+            assert_close(peaks.iter().sum(), sum);
+            assert_close(peaks.iter().copied().fold(0.0, f64::max), max);
+        }
+
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let aim = DifficultyValues::calculate(&Difficulty::new(), &map)
+            .skills
+            .aim;
+
+        check(
+            &aim.all,
+            1902.2660957867652,
+            62.88646567771584,
+            27474.7860398304,
+            218.01371464480374,
+        );
+        check(
+            &aim.raw,
+            1725.7679857762798,
+            62.8864656777159,
+            24925.59073023998,
+            197.78573041252508,
+        );
+        check(
+            &aim.jump,
+            1406.799105396458,
+            58.77297417226141,
+            21777.864243171065,
+            157.7493780539947,
+        );
+        check(
+            &aim.flow,
+            1349.2727862423476,
+            26.898242838084773,
+            7600.945619541676,
+            166.14308922107924,
+        );
+    }
+
+    #[test]
+    fn precision_weight() {
+        let radius = ScalingFactor::new(4.0).radius;
+        let x = |diameters: f64| (16.0 + diameters * 2.0 * radius).round();
+
+        // Second object stacked on the first, then three diameters at a
+        // sharp angle, then another three diameters in a straight line.
+        // Objects are far apart in time so they aren't stacked.
+        let hit_objects = format!(
+            "16,192,1000,1,0\n16,192,3000,1,0\n{},192,5000,1,0\n{},192,7000,1,0\n",
+            x(3.0),
+            x(6.0)
+        );
+
+        with_diff_objects(&hit_objects, |diff_objects| {
+            let weights: Vec<_> = diff_objects
+                .iter()
+                .map(|curr| AimEvaluator::calc_precision_weight(curr, radius))
+                .collect();
+
+            assert!(weights[0].abs() < f64::EPSILON);
+            assert!((weights[1] - 1.5).abs() < 1e-2, "{}", weights[1]);
+            assert!((weights[2] - 1.0).abs() < 1e-2, "{}", weights[2]);
+        });
+    }
+
+    #[test]
+    fn stacked_objects_have_no_precision_strain() {
+        let hit_objects: String = (1..=8)
+            .map(|i| format!("256,192,{},1,0\n", i * 1000))
+            .collect();
+
+        let map = parse(&hit_objects);
+        let aim = DifficultyValues::calculate(&Difficulty::new(), &map)
+            .skills
+            .aim;
+
+        let strains = aim.precision.object_strains();
+        assert!(strains.iter().all(|strain| strain.abs() < f64::EPSILON));
+    }
+
+    /// Precision of the fixture with the spacing and angle weighting.
+    #[test]
+    fn precision_fixture() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let attrs = Difficulty::new().calculate_for_mode::<Osu>(&map).unwrap();
+
+        assert_close(attrs.precision, 0.9210610551402105);
+        assert_close(attrs.precision_difficult_strain_count, 50.51046807520071);
+    }
+
+    #[test]
+    fn hidden_bonus_scales_with_ar() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let hidden_bonus = |ar: f32| {
+            let aim = |mods: u32| {
+                Difficulty::new()
+                    .mods(mods)
+                    .ar(ar, false)
+                    .calculate_for_mode::<Osu>(&map)
+                    .unwrap()
+                    .aim
+            };
+
+            aim(8) / aim(0)
+        };
+
+        let low_ar = hidden_bonus(5.0);
+        let high_ar = hidden_bonus(10.0);
+
+        assert!(high_ar > 1.0);
+        assert!(low_ar > high_ar);
+    }
+
+    #[test]
+    fn hidden_strain_ignores_fading_in() {
+        const FADE_IN: f64 = 240.0;
+
+        // Hidden strain while hitting the second object
+        let hidden_strain = |hit_objects: &str| {
+            with_diff_objects(hit_objects, |diff_objects| {
+                AimEvaluator::calc_hidden_strain(&diff_objects[0], diff_objects, PREEMPT, FADE_IN)
+            })
+        };
+
+        let alone = hidden_strain("100,100,1000,1,0\n300,100,2000,1,0\n");
+
+        // Fading in since 1900 and starts fading out at 2140
+        let fading_in = hidden_strain("100,100,1000,1,0\n300,100,2000,1,0\n300,300,2500,1,0\n");
+
+        // Started fading out at 1940
+        let fading_out = hidden_strain("100,100,1000,1,0\n300,100,2000,1,0\n300,300,2300,1,0\n");
+
+        assert!(alone > 0.0);
+        assert!((fading_in - alone).abs() < f64::EPSILON);
+        assert!(fading_out > alone);
+    }
+
+    #[test]
+    fn low_ar_multiplier() {
+        let preempt = |ar: f32| {
+            BeatmapAttributesBuilder::new()
+                .ar(ar, false)
+                .build()
+                .hit_windows
+                .ar
+        };
+
+        for ar in [9.3, 10.0, 11.0] {
+            for n_on_screen in 0..=16 {
+                let multiplier = AimEvaluator::calc_low_ar_multiplier(preempt(ar), n_on_screen);
+                assert!((multiplier - 1.0).abs() < f64::EPSILON);
+            }
+        }
+
+        for ar in [5.0, 6.0, 7.0] {
+            let multipliers: Vec<_> = (0..=16)
+                .map(|n_on_screen| AimEvaluator::calc_low_ar_multiplier(preempt(ar), n_on_screen))
+                .collect();
+
+            assert!((multipliers[0] - 1.0).abs() < f64::EPSILON);
+            assert!(multipliers.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn low_ar_reading() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let calc = |ar: f32, mods: u32| {
+            Difficulty::new()
+                .mods(mods)
+                .ar(ar, false)
+                .calculate_for_mode::<Osu>(&map)
+                .unwrap()
+        };
+
+        let low_ar = calc(5.0, 0);
+
+        assert!(calc(9.0, 0).reading.abs() < f64::EPSILON);
+        assert!(calc(9.3, 0).reading.abs() < f64::EPSILON);
+        assert!(low_ar.reading > 0.0);
+
+        // Reading gets the same mod adjustments as aim
+        let td = calc(5.0, 4);
+        assert!((td.reading - low_ar.reading.powf(0.8)).abs() < 1e-9);
+        let rx = calc(5.0, 128);
+        assert!((rx.reading - low_ar.reading * 0.9).abs() < 1e-9);
+        let ap = calc(5.0, 8192);
+        assert!(ap.aim.abs() < f64::EPSILON);
+        assert!(ap.reading.abs() < f64::EPSILON);
+    }
 }
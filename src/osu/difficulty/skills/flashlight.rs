@@ -0,0 +1,171 @@
+use crate::{
+    any::difficulty::{
+        object::{HasStartTime, IDifficultyObject},
+        skills::strain_decay,
+    },
+    osu::difficulty::object::OsuDifficultyObject,
+    util::pplus,
+};
+
+use super::strain::OsuStrainSkill;
+
+/// Radius of the flashlight circle in osu!pixels for combo below 100.
+const FLASHLIGHT_BASE_RADIUS: f64 = 200.0;
+
+define_skill! {
+    #[derive(Clone)]
+    pub struct Flashlight: StrainSkill => [OsuDifficultyObject<'a>][OsuDifficultyObject<'a>] {
+        current_strain: f64 = 0.0,
+        has_hidden: bool,
+        scaling_factor: f64,
+        time_preempt: f64,
+        time_fade_in: f64,
+    }
+}
+
+impl Flashlight {
+    const SKILL_MULTIPLIER: f64 = 0.7;
+    const STRAIN_DECAY_BASE: f64 = 0.15;
+
+    fn calculate_initial_strain(
+        &mut self,
+        time: f64,
+        curr: &OsuDifficultyObject<'_>,
+        objects: &[OsuDifficultyObject<'_>],
+    ) -> f64 {
+        let prev_start_time = curr
+            .previous(0, objects)
+            .map_or(0.0, HasStartTime::start_time);
+
+        self.current_strain * strain_decay(time - prev_start_time, Self::STRAIN_DECAY_BASE)
+    }
+
+    fn strain_value_at(
+        &mut self,
+        curr: &OsuDifficultyObject<'_>,
+        objects: &[OsuDifficultyObject<'_>],
+    ) -> f64 {
+        self.current_strain *= strain_decay(curr.strain_time, Self::STRAIN_DECAY_BASE);
+        self.current_strain += FlashlightEvaluator::evaluate_diff_of(
+            curr,
+            objects,
+            self.has_hidden,
+            self.scaling_factor,
+            self.time_preempt,
+            self.time_fade_in,
+        ) * Self::SKILL_MULTIPLIER;
+
+        self.current_strain
+    }
+}
+
+impl OsuStrainSkill for Flashlight {}
+
+/// Radius of the flashlight circle in osu!pixels at the given combo.
+///
+/// The circle shrinks once the combo reaches 100 and again at 200.
+pub fn flashlight_radius(combo: u32) -> f64 {
+    if combo >= 200 {
+        FLASHLIGHT_BASE_RADIUS * 0.8
+    } else if combo >= 100 {
+        FLASHLIGHT_BASE_RADIUS * 0.9
+    } else {
+        FLASHLIGHT_BASE_RADIUS
+    }
+}
+
+struct FlashlightEvaluator;
+
+impl FlashlightEvaluator {
+    const MAX_OPACITY_BONUS: f64 = 0.4;
+    const HIDDEN_BONUS: f64 = 0.2;
+    const OUT_OF_RADIUS_BONUS: f64 = 0.5;
+
+    fn evaluate_diff_of<'a>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        hidden: bool,
+        scaling_factor: f64,
+        time_preempt: f64,
+        time_fade_in: f64,
+    ) -> f64 {
+        if curr.base.is_spinner() {
+            return 0.0;
+        }
+
+        let mut small_dist_nerf = 1.0;
+        let mut cumulative_strain_time = 0.0;
+        let mut result = 0.0;
+        let mut last_obj = curr;
+
+        // Iterating backwards in time from the current object
+        for i in 0..curr.idx.min(10) {
+            let Some(prev) = curr.previous(i, diff_objects) else {
+                break;
+            };
+
+            cumulative_strain_time += last_obj.strain_time;
+            last_obj = prev;
+
+            if prev.base.is_spinner() {
+                continue;
+            }
+
+            let jump_dist =
+                f64::from((curr.base.stacked_pos() - prev.base.stacked_end_pos()).length());
+
+            // Objects that can easily be seen within the flashlight circle
+            // don't need to be memorised
+            if i == 0 {
+                small_dist_nerf = (jump_dist / 75.0).min(1.0);
+            }
+
+            // Only the first object of a stack needs to be memorised
+            let stack_nerf = (prev.raw_jump_dist / 25.0).min(1.0);
+
+            // The less visible the current object was when the previous one
+            // had to be hit, the more it has to be memorised
+            let opacity_bonus = 1.0
+                + Self::MAX_OPACITY_BONUS
+                    * (1.0
+                        - curr.opacity_at(
+                            prev.base.start_time,
+                            hidden,
+                            time_preempt,
+                            time_fade_in,
+                        ));
+
+            result +=
+                stack_nerf * opacity_bonus * scaling_factor * jump_dist / cumulative_strain_time;
+        }
+
+        result = (small_dist_nerf * result).powf(2.0);
+
+        // Objects outside of the flashlight circle are not visible while
+        // aiming towards them
+        let radius = flashlight_radius(curr.combo);
+        result *= 1.0
+            + Self::OUT_OF_RADIUS_BONUS
+                * pplus::transition_to_true(curr.raw_jump_dist, radius * 0.5, radius * 0.5);
+
+        // No approach circles with hidden
+        if hidden {
+            result *= 1.0 + Self::HIDDEN_BONUS;
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radius_shrinks_with_combo() {
+        assert!((flashlight_radius(0) - 200.0).abs() < f64::EPSILON);
+        assert!((flashlight_radius(99) - 200.0).abs() < f64::EPSILON);
+        assert!((flashlight_radius(100) - 180.0).abs() < f64::EPSILON);
+        assert!((flashlight_radius(200) - 160.0).abs() < f64::EPSILON);
+    }
+}
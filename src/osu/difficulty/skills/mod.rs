@@ -7,28 +7,36 @@ use crate::{
     osu::object::OsuObject,
 };
 
-use self::{aim::Aim, speed::Speed};
+use self::{aim::Aim, flashlight::Flashlight, speed::Speed};
 
 use super::{
     object::OsuDifficultyObject, scaling_factor::ScalingFactor, HD_FADE_IN_DURATION_MULTIPLIER,
 };
 
 pub mod aim;
+pub mod flashlight;
 pub mod speed;
 pub mod stamina;
 pub mod strain;
 pub mod rhythm_complexity;
 
+#[derive(Clone)]
 pub struct OsuSkills {
     pub aim: Aim,
-    pub raw_aim: Aim,
-    pub jump_aim: Aim,
-    pub flow_aim: Aim,
     pub speed: Speed,
     pub stamina: Stamina,
     pub rhythm_complexity: RhythmComplexity,
+    pub flashlight: Flashlight,
 }
 
+snapshot_state!(OsuSkills {
+    aim,
+    speed,
+    stamina,
+    rhythm_complexity,
+    flashlight,
+});
+
 impl OsuSkills {
     pub fn new(
         mods: &GameMods,
@@ -47,38 +55,43 @@ impl OsuSkills {
         // * classically known, but it feels good.
         // * This adjustment is necessary for AR>10, otherwise TimePreempt can
         // * become smaller leading to hitcircles not fully fading in.
-        // let time_fade_in = if mods.hd() {
-        //     time_preempt * HD_FADE_IN_DURATION_MULTIPLIER
-        // } else {
-        //     400.0 * (time_preempt / OsuObject::PREEMPT_MIN).min(1.0)
-        // };
+        let time_fade_in = if mods.hd() {
+            time_preempt * HD_FADE_IN_DURATION_MULTIPLIER
+        } else {
+            400.0 * (time_preempt / OsuObject::PREEMPT_MIN).min(1.0)
+        };
 
-        let aim = Aim::new(scaling_factor.radius, mods.hd(), mods.fl(), aim::AimType::All);
-        let raw_aim = Aim::new(scaling_factor.radius, mods.hd(),mods.fl(), aim::AimType::Raw);
-        let jump_aim = Aim::new(scaling_factor.radius, mods.hd(),mods.fl(), aim::AimType::Jump);
-        let flow_aim = Aim::new(scaling_factor.radius, mods.hd(),mods.fl(), aim::AimType::Flow);
+        let aim = Aim::new(
+            scaling_factor.radius,
+            mods.hd(),
+            mods.fl(),
+            time_preempt,
+            time_fade_in,
+        );
         let speed = Speed::new();
         let stamina = Stamina::new();
         let rhythm_complexity = RhythmComplexity::new(!mods.no_slider_head_acc(lazer));
+        let flashlight = Flashlight::new(
+            mods.hd(),
+            f64::from(scaling_factor.factor),
+            time_preempt,
+            time_fade_in,
+        );
 
         Self {
             aim,
-            raw_aim,
-            jump_aim,
-            flow_aim,
             speed,
             stamina,
             rhythm_complexity,
+            flashlight,
         }
     }
 
     pub fn process(&mut self, curr: &OsuDifficultyObject<'_>, objects: &[OsuDifficultyObject<'_>]) {
         self.aim.process(curr, objects);
-        self.raw_aim.process(curr, objects);
-        self.jump_aim.process(curr, objects);
-        self.flow_aim.process(curr, objects);
         self.speed.process(curr, objects);
         self.stamina.process(curr, objects);
         self.rhythm_complexity.process(curr, objects);
+        self.flashlight.process(curr, objects);
     }
 }
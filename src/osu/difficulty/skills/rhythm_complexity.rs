@@ -21,6 +21,7 @@ use super::strain::OsuStrainSkill;
 pub struct RhythmComplexity {
     current_strain: f64,
     strain_skill_current_section_peak: f64,
+    strain_skill_current_section_end: f64,
     strain_skill_strain_peaks: StrainsVec,
     strain_skill_object_strains: Vec<f64>,
     note_index: i32,
@@ -31,15 +32,46 @@ pub struct RhythmComplexity {
     is_previous_offbeat: bool,
     prev_doubles: Vec<i32>,
     is_slider_acc: bool,
+    /// Rhythm bonus of the most recently processed object.
+    pub last_rhythm_bonus: f64,
     pub flow_total: f64,
     pub jump_total: f64,
+    /// Amount of processed objects.
+    pub object_count: u32,
+    /// Amount of processed objects that are mostly flow aim.
+    pub stream_count: u32,
+    /// Amount of processed objects that are mostly jump aim and don't overlap
+    /// with the previous object.
+    pub jump_count: u32,
 }
 
+snapshot_state!(RhythmComplexity {
+    current_strain,
+    strain_skill_current_section_peak,
+    strain_skill_current_section_end,
+    strain_skill_strain_peaks,
+    strain_skill_object_strains,
+    note_index,
+    difficulty_total,
+    difficulty_total_slider_acc,
+    hit_circle_count,
+    accuracy_object_count,
+    is_previous_offbeat,
+    prev_doubles,
+    last_rhythm_bonus,
+    flow_total,
+    jump_total,
+    object_count,
+    stream_count,
+    jump_count,
+});
+
 impl RhythmComplexity {
     pub fn new(is_slider_acc: bool) -> Self {
         Self {
             current_strain: 0.0,
             strain_skill_current_section_peak: 0.0,
+            strain_skill_current_section_end: 0.0,
             strain_skill_strain_peaks: StrainsVec::with_capacity(256),
             strain_skill_object_strains: Vec::with_capacity(256),
             note_index: 0,
@@ -50,8 +82,12 @@ impl RhythmComplexity {
             is_previous_offbeat: false,
             prev_doubles: Vec::with_capacity(256),
             is_slider_acc,
+            last_rhythm_bonus: 0.0,
             flow_total: 0.0,
             jump_total: 0.0,
+            object_count: 0,
+            stream_count: 0,
+            jump_count: 0,
         }
     }
 }
@@ -65,24 +101,59 @@ impl StrainSkill for RhythmComplexity {
         curr: &Self::DifficultyObject<'a>,
         objects: &Self::DifficultyObjects<'a>,
     ) {
+        let section_length = f64::from(Self::SECTION_LENGTH);
+
+        // * The first object doesn't generate a strain, so we begin with an incremented section end
+        if curr.idx == 0 {
+            self.strain_skill_current_section_end =
+                f64::ceil(curr.start_time / section_length) * section_length;
+        }
+
+        while curr.start_time > self.strain_skill_current_section_end {
+            self.save_current_peak();
+            self.start_new_section_from(self.strain_skill_current_section_end, curr, objects);
+            self.strain_skill_current_section_end += section_length;
+        }
+
         self.flow_total += curr.flow;
         self.jump_total += curr.jump_dist;
+        self.object_count += 1;
+
+        if curr.flow > 0.5 {
+            self.stream_count += 1;
+        } else if curr.jump_dist > f64::from(OsuDifficultyObject::NORMALIZED_DIAMETER) {
+            self.jump_count += 1;
+        }
 
-        if curr.base.is_circle() {
+        let rhythm_bonus = if curr.base.is_circle() {
             let bonus = self.calc_rhythm_bonus(curr, objects);
             self.difficulty_total += bonus;
             self.difficulty_total_slider_acc += bonus;
             self.hit_circle_count += 1;
             self.accuracy_object_count += 1;
+
+            bonus
         } else if self.is_slider_acc && curr.base.is_slider() {
             let bonus = self.calc_rhythm_bonus(curr, objects);
             self.difficulty_total_slider_acc += bonus;
             self.accuracy_object_count += 1;
+
+            bonus
         } else {
             self.is_previous_offbeat = false;
-        }
+
+            0.0
+        };
 
         self.note_index += 1;
+        self.last_rhythm_bonus = rhythm_bonus;
+
+        let strain = self.strain_value_at(rhythm_bonus, curr);
+        self.strain_skill_current_section_peak =
+            f64::max(strain, self.strain_skill_current_section_peak);
+
+        // * Store the strain value for the object
+        self.strain_skill_object_strains.push(strain);
     }
 
     fn count_top_weighted_strains(&self, difficulty_value: f64) -> f64 {
@@ -92,6 +163,10 @@ impl StrainSkill for RhythmComplexity {
         )
     }
 
+    fn object_strains(&self) -> &[f64] {
+        &self.strain_skill_object_strains
+    }
+
     fn save_current_peak(&mut self) {
         self.strain_skill_strain_peaks
             .push(self.strain_skill_current_section_peak);
@@ -153,6 +228,13 @@ impl<'a> RhythmComplexity {
             * strain_decay(time - prev_start_time, Self::STRAIN_DECAY_BASE)
     }
 
+    fn strain_value_at(&mut self, rhythm_bonus: f64, curr: &OsuDifficultyObject<'_>) -> f64 {
+        self.current_strain *= strain_decay(curr.delta_time, Self::STRAIN_DECAY_BASE);
+        self.current_strain += rhythm_bonus;
+
+        self.current_strain
+    }
+
     fn calc_difficulty_value_for(difficulty: f64, object_count: i32) -> f64 {
         if object_count == 0 {
             return 1.0;
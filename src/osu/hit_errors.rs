@@ -0,0 +1,174 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{mode::ConvertError, replay::Replay},
+    Beatmap, Difficulty,
+};
+
+use super::{
+    attributes::OsuDifficultyAttributes, convert::convert_objects,
+    difficulty::scaling_factor::ScalingFactor,
+};
+
+/// Hit offsets of a [`Replay`] on an osu!standard map.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuHitErrors {
+    /// The hit offset of each hit object in milliseconds, adjusted by the
+    /// clock rate.
+    ///
+    /// Negative values are early hits, positive values late hits. Objects
+    /// that were not hit by a key press, e.g. misses or spinners, are `None`.
+    pub offsets: Vec<Option<f64>>,
+}
+
+impl OsuHitErrors {
+    /// Amount of objects that were hit by a key press.
+    pub fn n_hits(&self) -> usize {
+        self.offsets.iter().flatten().count()
+    }
+
+    /// The average hit offset.
+    ///
+    /// Returns `None` if no object was hit.
+    pub fn mean(&self) -> Option<f64> {
+        let n = self.n_hits();
+
+        if n == 0 {
+            return None;
+        }
+
+        Some(self.offsets.iter().flatten().sum::<f64>() / n as f64)
+    }
+
+    /// The unstable rate i.e. ten times the standard deviation of the hit
+    /// offsets.
+    ///
+    /// Returns `None` if fewer than two objects were hit.
+    pub fn unstable_rate(&self) -> Option<f64> {
+        let n = self.n_hits();
+
+        if n < 2 {
+            return None;
+        }
+
+        let mean = self.mean()?;
+
+        let variance = self
+            .offsets
+            .iter()
+            .flatten()
+            .map(|offset| (offset - mean) * (offset - mean))
+            .sum::<f64>()
+            / n as f64;
+
+        Some(variance.sqrt() * 10.0)
+    }
+}
+
+pub fn hit_errors(replay: &Replay, map: &Beatmap) -> Result<OsuHitErrors, ConvertError> {
+    let difficulty = Difficulty::new().mods(replay.mods);
+    let mods = difficulty.get_mods();
+    let map = map.convert_ref(GameMode::Osu, mods)?;
+
+    let clock_rate = difficulty.get_clock_rate();
+    let map_attrs = map.attributes().difficulty(&difficulty).build();
+    let scaling_factor = ScalingFactor::new(map_attrs.cs);
+
+    // Frames are in map time so hit windows must not be adjusted by the
+    // clock rate.
+    let meh_window = map_attrs.hit_windows.od_meh.unwrap_or(0.0) * clock_rate;
+    let time_preempt = f64::from((map_attrs.hit_windows.ar * clock_rate) as f32);
+
+    let objects = convert_objects(
+        &map,
+        &scaling_factor,
+        mods.reflection(),
+        time_preempt,
+        usize::MAX,
+        &mut OsuDifficultyAttributes::default(),
+    );
+
+    let radius = scaling_factor.radius;
+    let mut offsets = vec![None; objects.len()];
+
+    // Index of the next object that has not been judged yet
+    let mut next = 0;
+    let mut prev_keys = 0;
+
+    for frame in replay.frames.iter() {
+        let mut presses = frame.new_presses(prev_keys);
+        prev_keys = frame.keys;
+
+        while presses > 0 {
+            // Skip spinners and objects whose hit window has passed
+            while objects
+                .get(next)
+                .is_some_and(|h| h.is_spinner() || h.start_time + meh_window < frame.time)
+            {
+                next += 1;
+            }
+
+            let Some(h) = objects.get(next) else {
+                break;
+            };
+
+            let offset = frame.time - h.start_time;
+
+            if offset < -meh_window {
+                break;
+            }
+
+            let pos = h.stacked_pos();
+            let dx = f64::from(frame.x - pos.x);
+            let dy = f64::from(frame.y - pos.y);
+
+            if dx * dx + dy * dy <= radius * radius {
+                offsets[next] = Some(offset / clock_rate);
+                next += 1;
+            }
+
+            presses -= 1;
+        }
+    }
+
+    Ok(OsuHitErrors { offsets })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replay() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let replay = Replay::from_path("./resources/2785319.osr").unwrap();
+
+        let hit_errors = replay.osu_hit_errors(&map).unwrap();
+
+        // All objects but the spinner were hit
+        assert_eq!(hit_errors.offsets.len(), 601);
+        assert_eq!(hit_errors.n_hits(), 600);
+
+        // Offsets are adjusted by the clock rate of DT
+        assert_eq!(hit_errors.offsets[0], Some(-8.0));
+        assert_eq!(hit_errors.offsets[1], Some(4.0 / 1.5));
+
+        let mean = hit_errors.mean().unwrap();
+        assert!((mean - 1.75 / 1.5).abs() < 1e-9);
+
+        let unstable_rate = hit_errors.unstable_rate().unwrap();
+        assert!((unstable_rate - 54.543_560_573_178_574).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_hits() {
+        let hit_errors = OsuHitErrors {
+            offsets: vec![None, Some(5.0), None],
+        };
+
+        assert_eq!(hit_errors.n_hits(), 1);
+        assert!((hit_errors.mean().unwrap() - 5.0).abs() < f64::EPSILON);
+        assert!(hit_errors.unstable_rate().is_none());
+        assert!(OsuHitErrors::default().mean().is_none());
+    }
+}
@@ -0,0 +1,42 @@
+/// A single judgement of an osu!standard score.
+///
+/// Used to feed [`OsuGradualPerformance::judge`].
+///
+/// [`OsuGradualPerformance::judge`]: crate::osu::OsuGradualPerformance::judge
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum HitResult {
+    /// A 300 on a hit object.
+    Great,
+    /// A 100 on a hit object.
+    Ok,
+    /// A 50 on a hit object.
+    Meh,
+    /// A miss on a hit object.
+    Miss,
+    /// A hit slider tick or repeat.
+    SliderTickHit,
+    /// A missed slider tick or repeat.
+    SliderTickMiss,
+    /// A hit slider end.
+    SliderEndHit,
+    /// A missed slider end.
+    SliderEndMiss,
+    /// A bonus spin on a spinner.
+    SpinnerBonus,
+}
+
+impl HitResult {
+    /// Whether the judgement belongs to a hit object itself rather than to
+    /// one of its nested objects.
+    pub const fn is_object(self) -> bool {
+        matches!(self, Self::Great | Self::Ok | Self::Meh | Self::Miss)
+    }
+
+    /// Whether the judgement increments the combo.
+    pub const fn is_hit(self) -> bool {
+        matches!(
+            self,
+            Self::Great | Self::Ok | Self::Meh | Self::SliderTickHit | Self::SliderEndHit
+        )
+    }
+}
@@ -9,20 +9,46 @@ use crate::{
 };
 
 pub use self::{
-    attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    attributes::{
+        OsuDifficultyAttributes, OsuPerformanceAttributes, OsuPerformanceBreakdown,
+        OsuSkillBreakdown,
+    },
+    config::{PpPlusConfig, ScoringModel},
     difficulty::gradual::OsuGradualDifficulty,
+    hit_errors::OsuHitErrors,
+    hit_result::HitResult,
+    object_info::OsuObjectInfo,
     performance::{gradual::OsuGradualPerformance, OsuPerformance},
     score_state::{OsuScoreOrigin, OsuScoreState},
     strains::OsuStrains,
+    style::OsuMapStyle,
+    vanilla::{OsuVanillaDifficultyAttributes, OsuVanillaPerformanceAttributes},
 };
 
 mod attributes;
+mod config;
 mod convert;
 mod difficulty;
+mod hit_errors;
+mod hit_result;
 mod object;
+mod object_info;
 mod performance;
 mod score_state;
 mod strains;
+mod style;
+mod vanilla;
+
+/// Pattern segmentation of osu!standard maps.
+pub mod patterns;
+
+/// Aggregation of a player's PP+ scores into per-skill totals.
+pub mod profile;
+
+pub(crate) use self::{
+    hit_errors::hit_errors, object_info::object_infos,
+    vanilla::difficulty::difficulty as vanilla_difficulty,
+};
 
 const PLAYFIELD_BASE_SIZE: Pos = Pos::new(512.0, 384.0);
 
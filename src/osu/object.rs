@@ -175,6 +175,14 @@ impl OsuObject {
     pub const fn is_spinner(&self) -> bool {
         matches!(self.kind, OsuObjectKind::Spinner(_))
     }
+
+    /// The amount of combo this object awards when hit.
+    pub const fn max_combo(&self) -> u32 {
+        match self.kind {
+            OsuObjectKind::Circle | OsuObjectKind::Spinner(_) => 1,
+            OsuObjectKind::Slider(ref slider) => 1 + slider.nested_objects.len() as u32,
+        }
+    }
 }
 
 pub enum OsuObjectKind {
@@ -0,0 +1,138 @@
+use rosu_map::{section::general::GameMode, util::Pos};
+
+use crate::{any::difficulty::skills::StrainSkill, model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::difficulty::{skills::OsuSkills, DifficultyValues};
+
+/// Difficulty internals of a single osu! hit object.
+///
+/// The first hit object of a map has no difficulty values so a map with `n`
+/// hit objects produces `n - 1` of these.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuObjectInfo {
+    /// Index of the hit object within the map.
+    pub idx: usize,
+    /// Start time of the hit object, adjusted by the clock rate.
+    pub time: f64,
+    /// Stacked position of the hit object.
+    pub pos: Pos,
+    /// How much the movement towards the object is considered flow aim.
+    pub flow: f64,
+    /// The flow value before it was adjusted for surrounding objects.
+    pub base_flow: f64,
+    /// Normalized distance to the previous object's cursor position.
+    pub jump_dist: f64,
+    /// Normalized distance to the previous object's end position without
+    /// any adjustments.
+    pub raw_jump_dist: f64,
+    /// Angle between the previous, current, and next object in radians.
+    pub angle: Option<f64>,
+    /// How lenient the angle is when moving towards the object.
+    pub angle_leniency: f64,
+    /// Time to the previous object, adjusted by the clock rate and with a
+    /// lower limit.
+    pub strain_time: f64,
+    /// Time to the object before the previous one, adjusted by the clock rate
+    /// and with a lower limit.
+    pub last_two_strain_time: f64,
+    /// Strain of the aim skill after this object.
+    pub aim: f64,
+    /// Strain of the raw aim skill after this object.
+    pub raw_aim: f64,
+    /// Strain of the jump aim skill after this object.
+    pub jump_aim: f64,
+    /// Strain of the flow aim skill after this object.
+    pub flow_aim: f64,
+    /// Strain of the precision skill after this object.
+    pub precision: f64,
+    /// Strain of the low approach rate reading skill after this object.
+    pub reading: f64,
+    /// Strain of the speed skill after this object.
+    pub speed: f64,
+    /// Strain of the stamina skill after this object.
+    pub stamina: f64,
+    /// Strain of the accuracy skill after this object.
+    pub accuracy: f64,
+    /// Strain of the flashlight skill after this object.
+    pub flashlight: f64,
+}
+
+pub fn object_infos(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<Vec<OsuObjectInfo>, ConvertError> {
+    let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+    let mut infos = Vec::with_capacity(map.hit_objects.len().saturating_sub(1));
+
+    DifficultyValues::calculate_with(difficulty, &map, |curr, skills| {
+        let OsuSkills {
+            aim,
+            speed,
+            stamina,
+            rhythm_complexity,
+            flashlight,
+        } = skills;
+
+        infos.push(OsuObjectInfo {
+            idx: curr.idx + 1,
+            time: curr.start_time,
+            pos: curr.base.stacked_pos(),
+            flow: curr.flow,
+            base_flow: curr.base_flow,
+            jump_dist: curr.jump_dist,
+            raw_jump_dist: curr.raw_jump_dist,
+            angle: curr.angle,
+            angle_leniency: curr.angle_leniency,
+            strain_time: curr.strain_time,
+            last_two_strain_time: curr.last_two_strain_time,
+            aim: last_strain(&aim.all),
+            raw_aim: last_strain(&aim.raw),
+            jump_aim: last_strain(&aim.jump),
+            flow_aim: last_strain(&aim.flow),
+            precision: last_strain(&aim.precision),
+            reading: last_strain(&aim.reading),
+            speed: last_strain(speed),
+            stamina: last_strain(stamina),
+            accuracy: last_strain(rhythm_complexity),
+            flashlight: last_strain(flashlight),
+        });
+    });
+
+    Ok(infos)
+}
+
+fn last_strain(skill: &impl StrainSkill) -> f64 {
+    skill.object_strains().last().copied().unwrap_or(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{osu::Osu, Beatmap, Difficulty};
+
+    #[test]
+    fn object_infos() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let difficulty = Difficulty::new().mods(64);
+
+        let infos = difficulty.osu_objects(&map).unwrap();
+        assert_eq!(infos.len(), map.hit_objects.len() - 1);
+
+        let strains = difficulty.strains_for_mode::<Osu>(&map).unwrap();
+        let max_aim = strains.aim.iter().copied().fold(0.0, f64::max);
+        let max_info_aim = infos.iter().map(|info| info.aim).fold(0.0, f64::max);
+        assert!((max_aim - max_info_aim).abs() < f64::EPSILON);
+
+        for (info, h) in infos.iter().zip(map.hit_objects.iter().skip(1)) {
+            assert!((info.time - h.start_time / 1.5).abs() < 1e-6);
+            assert!(info.strain_time >= 25.0);
+        }
+    }
+
+    #[test]
+    fn object_infos_convert_error() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        assert!(Difficulty::new().osu_objects(&map).is_err());
+    }
+}
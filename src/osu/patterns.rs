@@ -0,0 +1,542 @@
+//! The segmentation is based on the same flow values that the PP+ aim skills
+//! use so a stream in this module is also a stream for the difficulty
+//! calculation.
+//!
+//! # Example
+//!
+//! ```
+//! use rosu_pp::{Beatmap, Difficulty};
+//! use rosu_pp::osu::patterns::PatternKind;
+//!
+//! let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+//!
+//! let patterns = Difficulty::new().osu_patterns(&map).unwrap();
+//!
+//! for segment in patterns.segments.iter() {
+//!     if segment.kind == PatternKind::Stream {
+//!         println!("{} notes at {:.0} BPM", segment.n_objects, segment.bpm);
+//!     }
+//! }
+//!
+//! println!("Bursts: {}", patterns.n_bursts());
+//! ```
+
+use rosu_map::section::{general::GameMode, hit_objects::CurveBuffers};
+
+use crate::{model::mode::ConvertError, Beatmap, Difficulty};
+
+use super::{
+    difficulty::{object::OsuDifficultyObject, DifficultyValues},
+    object::OsuObject,
+};
+
+/// The kind of a [`PatternSegment`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PatternKind {
+    /// Up to [`OsuPatterns::BURST_MAX_OBJECTS`] consecutive flow aim notes.
+    Burst,
+    /// Consecutive flow aim notes.
+    Stream,
+    /// At least [`OsuPatterns::DEATHSTREAM_MIN_OBJECTS`] consecutive flow aim
+    /// notes.
+    Deathstream,
+    /// Consecutive spaced jumps.
+    JumpChain,
+    /// Consecutive sliders that are neither part of a stream nor a jump chain.
+    SliderSection,
+    /// A pause of at least [`OsuPatterns::BREAK_MIN_GAP`] milliseconds
+    /// between two objects.
+    Break,
+    /// Objects that don't form any of the other patterns.
+    Mixed,
+}
+
+impl PatternKind {
+    /// Whether the kind is a burst, stream, or deathstream.
+    pub const fn is_stream(self) -> bool {
+        matches!(self, Self::Burst | Self::Stream | Self::Deathstream)
+    }
+}
+
+/// A contiguous section of a map.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternSegment {
+    /// The kind of pattern.
+    pub kind: PatternKind,
+    /// Index of the segment's first hit object.
+    pub start_idx: usize,
+    /// Amount of hit objects in the segment. Always `0` for breaks.
+    pub n_objects: usize,
+    /// Start time of the segment, adjusted by the clock rate.
+    pub start_time: f64,
+    /// End time of the segment, adjusted by the clock rate.
+    pub end_time: f64,
+    /// BPM of the segment's notes.
+    ///
+    /// Stream-like segments are assumed to consist of 1/4 notes, all other
+    /// segments of 1/2 notes. Segments with less than two objects have a BPM
+    /// of `0.0`.
+    pub bpm: f64,
+    /// Mean distance in osu!pixels between consecutive objects of the segment.
+    pub mean_spacing: f64,
+}
+
+/// The segmentation of an osu!standard map into patterns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuPatterns {
+    /// All segments of the map in chronological order.
+    ///
+    /// Together, the segments cover all hit objects of the map.
+    pub segments: Vec<PatternSegment>,
+}
+
+impl OsuPatterns {
+    /// The maximum amount of notes in a [`PatternKind::Burst`].
+    pub const BURST_MAX_OBJECTS: usize = 8;
+    /// The minimum amount of notes in a [`PatternKind::Deathstream`].
+    pub const DEATHSTREAM_MIN_OBJECTS: usize = 32;
+    /// The minimum amount of jumps in a [`PatternKind::JumpChain`].
+    pub const JUMP_CHAIN_MIN_OBJECTS: usize = 3;
+    /// The minimum gap in milliseconds between two objects to be considered a
+    /// [`PatternKind::Break`].
+    pub const BREAK_MIN_GAP: f64 = 1500.0;
+
+    /// Minimum flow for a note to be considered part of a stream.
+    const STREAM_FLOW: f64 = 0.5;
+    /// Maximum relative difference of delta times within a stream.
+    const STREAM_RHYTHM_TOLERANCE: f64 = 0.15;
+    /// Minimum amount of stream notes to be considered a burst.
+    const STREAM_MIN_OBJECTS: usize = 3;
+    /// Minimum amount of sliders to be considered a slider section.
+    const SLIDER_SECTION_MIN_OBJECTS: usize = 2;
+
+    /// The longest burst, stream, or deathstream.
+    pub fn longest_stream(&self) -> Option<&PatternSegment> {
+        self.segments
+            .iter()
+            .filter(|segment| segment.kind.is_stream())
+            .max_by_key(|segment| segment.n_objects)
+    }
+
+    /// The amount of 1/4 bursts.
+    pub fn n_bursts(&self) -> usize {
+        self.count(PatternKind::Burst)
+    }
+
+    /// The amount of segments of the given kind.
+    pub fn count(&self, kind: PatternKind) -> usize {
+        self.segments
+            .iter()
+            .filter(|segment| segment.kind == kind)
+            .count()
+    }
+
+    fn from_objects(objects: &[PatternObject]) -> Self {
+        let mut labels: Vec<_> = objects.iter().map(PatternObject::label).collect();
+
+        // The first note of a stream is reached through a jump or a slider
+        // so it needs to be pulled into the stream explicitly.
+        for i in 1..labels.len() {
+            if labels[i] == Label::Stream
+                && labels[i - 1] != Label::Stream
+                && !objects[i].after_break
+            {
+                labels[i - 1] = Label::Stream;
+            }
+        }
+
+        let mut segments = Vec::new();
+        let mut mixed: Option<Group> = None;
+        let mut group: Option<Group> = None;
+
+        for (i, (obj, &label)) in objects.iter().zip(labels.iter()).enumerate() {
+            if obj.after_break {
+                if let Some(group) = group.take() {
+                    group.finish(&mut segments, &mut mixed);
+                }
+
+                if let Some(mixed) = mixed.take() {
+                    segments.push(mixed.into_segment());
+                }
+
+                if let Some(prev) = i.checked_sub(1).map(|j| &objects[j]) {
+                    segments.push(PatternSegment {
+                        kind: PatternKind::Break,
+                        start_idx: i,
+                        n_objects: 0,
+                        start_time: prev.end_time,
+                        end_time: obj.start_time,
+                        bpm: 0.0,
+                        mean_spacing: 0.0,
+                    });
+                }
+            }
+
+            match group {
+                Some(ref mut group) if group.accepts(obj, label) => group.push(obj),
+                _ => {
+                    if let Some(group) = group.take() {
+                        group.finish(&mut segments, &mut mixed);
+                    }
+
+                    group = Some(Group::new(i, obj, label));
+                }
+            }
+        }
+
+        if let Some(group) = group {
+            group.finish(&mut segments, &mut mixed);
+        }
+
+        if let Some(mixed) = mixed {
+            segments.push(mixed.into_segment());
+        }
+
+        Self { segments }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Label {
+    Stream,
+    Jump,
+    Slider,
+    Other,
+}
+
+struct PatternObject {
+    start_time: f64,
+    end_time: f64,
+    delta_time: f64,
+    flow: f64,
+    jump_dist: f64,
+    raw_jump_dist: f64,
+    is_slider: bool,
+    is_first: bool,
+    after_break: bool,
+}
+
+impl PatternObject {
+    fn label(&self) -> Label {
+        if self.is_first || self.after_break {
+            Label::Other
+        } else if self.flow > OsuPatterns::STREAM_FLOW {
+            Label::Stream
+        } else if self.jump_dist > f64::from(OsuDifficultyObject::NORMALIZED_DIAMETER) {
+            Label::Jump
+        } else if self.is_slider {
+            Label::Slider
+        } else {
+            Label::Other
+        }
+    }
+}
+
+struct Group {
+    label: Label,
+    start_idx: usize,
+    n_objects: usize,
+    start_time: f64,
+    end_time: f64,
+    rhythm: Option<f64>,
+    first_delta: f64,
+    first_spacing: f64,
+    delta_sum: f64,
+    spacing_sum: f64,
+}
+
+impl Group {
+    const fn new(start_idx: usize, obj: &PatternObject, label: Label) -> Self {
+        Self {
+            label,
+            start_idx,
+            n_objects: 1,
+            start_time: obj.start_time,
+            end_time: obj.end_time,
+            rhythm: None,
+            first_delta: obj.delta_time,
+            first_spacing: obj.raw_jump_dist,
+            delta_sum: 0.0,
+            spacing_sum: 0.0,
+        }
+    }
+
+    fn accepts(&self, obj: &PatternObject, label: Label) -> bool {
+        if label != self.label || obj.after_break {
+            return false;
+        }
+
+        match (label, self.rhythm) {
+            (Label::Stream, Some(rhythm)) => {
+                (obj.delta_time - rhythm).abs() <= rhythm * OsuPatterns::STREAM_RHYTHM_TOLERANCE
+            }
+            _ => true,
+        }
+    }
+
+    fn push(&mut self, obj: &PatternObject) {
+        self.rhythm.get_or_insert(obj.delta_time);
+        self.n_objects += 1;
+        self.end_time = obj.end_time;
+        self.delta_sum += obj.delta_time;
+        self.spacing_sum += obj.raw_jump_dist;
+    }
+
+    /// Push the group as segment or, if it doesn't form a pattern, merge it
+    /// into the pending mixed group.
+    fn finish(self, segments: &mut Vec<PatternSegment>, mixed: &mut Option<Group>) {
+        if self.kind() != PatternKind::Mixed {
+            if let Some(mixed) = mixed.take() {
+                segments.push(mixed.into_segment());
+            }
+
+            segments.push(self.into_segment());
+        } else if let Some(mixed) = mixed {
+            mixed.n_objects += self.n_objects;
+            mixed.end_time = self.end_time;
+            mixed.delta_sum += self.first_delta + self.delta_sum;
+            mixed.spacing_sum += self.first_spacing + self.spacing_sum;
+        } else {
+            *mixed = Some(Self {
+                label: Label::Other,
+                ..self
+            });
+        }
+    }
+
+    const fn kind(&self) -> PatternKind {
+        match self.label {
+            Label::Stream if self.n_objects < OsuPatterns::STREAM_MIN_OBJECTS => PatternKind::Mixed,
+            Label::Stream if self.n_objects <= OsuPatterns::BURST_MAX_OBJECTS => PatternKind::Burst,
+            Label::Stream if self.n_objects >= OsuPatterns::DEATHSTREAM_MIN_OBJECTS => {
+                PatternKind::Deathstream
+            }
+            Label::Stream => PatternKind::Stream,
+            Label::Jump if self.n_objects >= OsuPatterns::JUMP_CHAIN_MIN_OBJECTS => {
+                PatternKind::JumpChain
+            }
+            Label::Slider if self.n_objects >= OsuPatterns::SLIDER_SECTION_MIN_OBJECTS => {
+                PatternKind::SliderSection
+            }
+            Label::Jump | Label::Slider | Label::Other => PatternKind::Mixed,
+        }
+    }
+
+    fn into_segment(self) -> PatternSegment {
+        let kind = self.kind();
+
+        let n_transitions = self.n_objects - 1;
+
+        let (bpm, mean_spacing) = if n_transitions == 0 {
+            (0.0, 0.0)
+        } else {
+            let mean_delta = self.delta_sum / n_transitions as f64;
+            let beat_fraction = if kind.is_stream() { 15_000.0 } else { 30_000.0 };
+
+            (
+                beat_fraction / mean_delta.max(1.0),
+                self.spacing_sum / n_transitions as f64,
+            )
+        };
+
+        PatternSegment {
+            kind,
+            start_idx: self.start_idx,
+            n_objects: self.n_objects,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            bpm,
+            mean_spacing,
+        }
+    }
+}
+
+pub(crate) fn patterns(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<OsuPatterns, ConvertError> {
+    let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+    let mut objects = Vec::with_capacity(map.hit_objects.len());
+
+    // The first hit object has no difficulty object
+    if let Some(h) = map
+        .hit_objects
+        .first()
+        .filter(|_| difficulty.get_passed_objects() > 0)
+    {
+        let first = OsuObject::new(h, &map, &mut CurveBuffers::default(), &mut Vec::new());
+        let clock_rate = difficulty.get_clock_rate();
+
+        objects.push(PatternObject {
+            start_time: first.start_time / clock_rate,
+            end_time: first.end_time() / clock_rate,
+            delta_time: 0.0,
+            flow: 0.0,
+            jump_dist: 0.0,
+            raw_jump_dist: 0.0,
+            is_slider: first.is_slider(),
+            is_first: true,
+            after_break: false,
+        });
+    }
+
+    DifficultyValues::calculate_with(difficulty, &map, |curr, _| {
+        let prev_end_time = objects
+            .last()
+            .map_or(curr.start_time - curr.delta_time, |prev: &PatternObject| {
+                prev.end_time
+            });
+
+        objects.push(PatternObject {
+            start_time: curr.start_time,
+            end_time: curr.end_time,
+            delta_time: curr.delta_time,
+            flow: curr.flow,
+            jump_dist: curr.jump_dist,
+            raw_jump_dist: curr.raw_jump_dist,
+            is_slider: curr.base.is_slider(),
+            is_first: false,
+            after_break: curr.start_time - prev_end_time >= OsuPatterns::BREAK_MIN_GAP,
+        });
+    });
+
+    Ok(OsuPatterns::from_objects(&objects))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(start_time: f64, flow: f64, jump_dist: f64) -> PatternObject {
+        PatternObject {
+            start_time,
+            end_time: start_time,
+            delta_time: 0.0,
+            flow,
+            jump_dist,
+            raw_jump_dist: jump_dist,
+            is_slider: false,
+            is_first: false,
+            after_break: false,
+        }
+    }
+
+    fn objects(specs: &[(f64, f64, f64)]) -> Vec<PatternObject> {
+        let mut prev_time = None;
+
+        let mut objects: Vec<_> = specs
+            .iter()
+            .map(|&(time, flow, jump_dist)| {
+                let mut obj = object(time, flow, jump_dist);
+                obj.delta_time = prev_time.map_or(0.0, |prev| time - prev);
+                obj.after_break = obj.delta_time >= OsuPatterns::BREAK_MIN_GAP;
+                prev_time = Some(time);
+
+                obj
+            })
+            .collect();
+
+        objects[0].is_first = true;
+
+        objects
+    }
+
+    #[test]
+    fn stream_and_jumps() {
+        let mut specs = vec![(0.0, 0.0, 0.0)];
+        specs.extend((1..16).map(|i| (f64::from(i) * 100.0, 1.0, 20.0)));
+        specs.extend((0..5).map(|i| (1800.0 + f64::from(i) * 200.0, 0.0, 250.0)));
+
+        let patterns = OsuPatterns::from_objects(&objects(&specs));
+        let kinds: Vec<_> = patterns.segments.iter().map(|s| s.kind).collect();
+
+        assert_eq!(kinds, [PatternKind::Stream, PatternKind::JumpChain]);
+
+        let stream = patterns.longest_stream().unwrap();
+        assert_eq!(stream.start_idx, 0);
+        assert_eq!(stream.n_objects, 16);
+        assert!((stream.bpm - 150.0).abs() < 1e-6);
+        assert_eq!(patterns.n_bursts(), 0);
+
+        let n_objects: usize = patterns.segments.iter().map(|s| s.n_objects).sum();
+        assert_eq!(n_objects, specs.len());
+    }
+
+    #[test]
+    fn bursts_and_breaks() {
+        let mut specs = vec![(0.0, 0.0, 0.0)];
+        specs.extend((1..5).map(|i| (f64::from(i) * 100.0, 1.0, 20.0)));
+        specs.push((5000.0, 0.0, 300.0));
+        specs.push((5500.0, 0.0, 50.0));
+        specs.extend((1..5).map(|i| (5500.0 + f64::from(i) * 100.0, 1.0, 20.0)));
+
+        let patterns = OsuPatterns::from_objects(&objects(&specs));
+        let kinds: Vec<_> = patterns.segments.iter().map(|s| s.kind).collect();
+
+        assert_eq!(
+            kinds,
+            [
+                PatternKind::Burst,
+                PatternKind::Break,
+                PatternKind::Mixed,
+                PatternKind::Burst
+            ]
+        );
+        assert_eq!(patterns.n_bursts(), 2);
+        assert_eq!(patterns.count(PatternKind::Break), 1);
+    }
+
+    fn parse(hit_objects: &str) -> Beatmap {
+        let content = format!(
+            "osu file format v14\n\n[General]\nMode: 0\n\n[Difficulty]\n\
+            HPDrainRate:5\nCircleSize:4\nOverallDifficulty:8\nApproachRate:9\n\
+            SliderMultiplier:1.4\nSliderTickRate:1\n\n[TimingPoints]\n\
+            0,500,4,2,0,100,1,0\n\n[HitObjects]\n{hit_objects}"
+        );
+
+        Beatmap::from_bytes(content.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn starts_with_long_slider() {
+        // The slider lasts longer than `BREAK_MIN_GAP`
+        let map = parse("256,192,0,2,0,L|256:392,1,1000\n100,100,4000,1,0\n");
+
+        let patterns = Difficulty::new().osu_patterns(&map).unwrap();
+
+        assert_eq!(patterns.count(PatternKind::Break), 0);
+
+        let first = &patterns.segments[0];
+        assert!(first.end_time > OsuPatterns::BREAK_MIN_GAP);
+
+        let n_objects: usize = patterns.segments.iter().map(|s| s.n_objects).sum();
+        assert_eq!(n_objects, 2);
+    }
+
+    #[test]
+    fn single_object() {
+        let map = parse("256,192,1000,1,0\n");
+
+        let patterns = Difficulty::new().osu_patterns(&map).unwrap();
+
+        assert_eq!(patterns.segments.len(), 1);
+        assert_eq!(patterns.segments[0].kind, PatternKind::Mixed);
+        assert_eq!(patterns.segments[0].n_objects, 1);
+        assert!((patterns.segments[0].start_time - 1000.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn map() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let patterns = Difficulty::new().osu_patterns(&map).unwrap();
+        let n_objects: usize = patterns.segments.iter().map(|s| s.n_objects).sum();
+
+        assert_eq!(n_objects, map.hit_objects.len());
+        assert!(patterns
+            .segments
+            .windows(2)
+            .all(|w| w[0].start_time <= w[1].start_time));
+    }
+}
@@ -5,7 +5,8 @@ use statrs::distribution::{Beta, ContinuousCDF, Normal};
 use crate::{
     osu::{
         difficulty::skills::{aim::Aim, speed::Speed, strain::OsuStrainSkill},
-        OsuDifficultyAttributes, OsuPerformanceAttributes, OsuScoreState,
+        OsuDifficultyAttributes, OsuPerformanceAttributes, OsuPerformanceBreakdown, OsuScoreOrigin,
+        OsuScoreState, OsuSkillBreakdown, PpPlusConfig,
     },
     util::{
         difficulty::reverse_lerp,
@@ -15,106 +16,171 @@ use crate::{
     GameMods,
 };
 
-use super::{n_large_tick_miss, n_slider_ends_dropped, total_imperfect_hits};
+use super::{
+    calculate_effective_miss_count, miss_positions::PositionalMisses, n_large_tick_miss,
+    n_slider_ends_dropped,
+};
 
 // * This is being adjusted to keep the final pp value scaled around what it used to be when changing things.
 pub const PERFORMANCE_BASE_MULTIPLIER: f64 = 1.12;
-pub const ENABLE_EFFECTIVE_MISS_COUNT: bool = true;
-pub const ENABLE_LENGTH_BONUS: bool = true;
-pub const ENABLE_CSR: bool = true; // Combo Scaling Rework
 
-pub(super) struct OsuPerformanceCalculator<'mods> {
+// PP+ has no aim variant without sliders so instead of lazer's slider factor
+// a fixed lower bound is used for the slider nerf.
+pub(super) const SLIDER_NERF_MIN_FACTOR: f64 = 0.9;
+
+/// Everything of a PP+ calculation that only depends on the map, mods, and
+/// config so that it can be shared across multiple score states.
+pub(super) struct OsuPerformanceSetup<'mods> {
     attrs: OsuDifficultyAttributes,
     mods: &'mods GameMods,
-    acc: f64,
-    state: OsuScoreState,
-    effective_miss_count: f64,
+    origin: OsuScoreOrigin,
     using_classic_slider_acc: bool,
+    hit_error: Option<f64>,
+    positional_misses: Option<PositionalMisses>,
+    config: PpPlusConfig,
+    accuracy_hit_objects_count: u32,
+    hit_window: f64,
+    relax_hit_multipliers: Option<(f64, f64)>,
+    accuracy_length_weight: f64,
+    accuracy_mod_weight: f64,
 }
 
-impl<'a> OsuPerformanceCalculator<'a> {
-    pub const fn new(
+impl<'a> OsuPerformanceSetup<'a> {
+    pub fn new(
         attrs: OsuDifficultyAttributes,
         mods: &'a GameMods,
-        acc: f64,
-        state: OsuScoreState,
-        effective_miss_count: f64,
+        origin: OsuScoreOrigin,
         using_classic_slider_acc: bool,
+        hit_error: Option<f64>,
+        positional_misses: Option<PositionalMisses>,
+        config: PpPlusConfig,
     ) -> Self {
+        // Calculate accuracy hit objects count
+        let mut accuracy_hit_objects_count = attrs.n_circles;
+        if !using_classic_slider_acc {
+            accuracy_hit_objects_count += attrs.n_sliders;
+        }
+
+        let od = attrs.od();
+        let hit_window = 79.5 - od * 6.0;
+
+        // 保留rx的计算
+        let relax_hit_multipliers = mods.rx().then(|| {
+            // * https://www.desmos.com/calculator/bc9eybdthb
+            // * we use OD13.3 as maximum since it's the value at which great hitwidow becomes 0
+            // * this is well beyond currently maximum achievable OD which is 12.17 (DTx2 + DA with OD11)
+            if od > 0.0 {
+                (
+                    (1.0 - (od / 13.33).powf(1.8)).max(0.0),
+                    (1.0 - (od / 13.33).powf(5.0)).max(0.0),
+                )
+            } else {
+                (1.0, 1.0)
+            }
+        });
+
+        let (accuracy_length_weight, accuracy_mod_weight) =
+            Self::calculate_accuracy_weights(mods, accuracy_hit_objects_count);
+
         Self {
             attrs,
             mods,
-            acc,
+            origin,
+            using_classic_slider_acc,
+            hit_error,
+            positional_misses,
+            config,
+            accuracy_hit_objects_count,
+            hit_window,
+            relax_hit_multipliers,
+            accuracy_length_weight,
+            accuracy_mod_weight,
+        }
+    }
+
+    pub fn calculate_breakdown(&self, state: OsuScoreState) -> OsuPerformanceBreakdown {
+        let effective_miss_count = if self.config.effective_miss_count {
+            calculate_effective_miss_count(&self.attrs, &state, &self.origin)
+        } else {
+            f64::from(state.misses)
+        };
+
+        let inner = OsuPerformanceCalculator {
+            setup: self,
             state,
             effective_miss_count,
-            using_classic_slider_acc,
+        };
+
+        inner.calculate_breakdown()
+    }
+
+    fn calculate_accuracy_weights(mods: &GameMods, accuracy_hit_objects_count: u32) -> (f64, f64) {
+        let length_weight = (f64::from(accuracy_hit_objects_count + 400) / 1050.0).tanh() * 1.2;
+
+        let mut mod_weight = 1.0;
+        if mods.hd() {
+            mod_weight *= 1.02;
         }
+        if mods.fl() {
+            mod_weight *= 1.04;
+        }
+
+        (length_weight, mod_weight)
     }
 }
 
+struct OsuPerformanceCalculator<'a> {
+    setup: &'a OsuPerformanceSetup<'a>,
+    state: OsuScoreState,
+    effective_miss_count: f64,
+}
+
 impl OsuPerformanceCalculator<'_> {
-    pub fn calculate(mut self) -> OsuPerformanceAttributes {
+    #[allow(clippy::too_many_lines)]
+    fn calculate_breakdown(mut self) -> OsuPerformanceBreakdown {
+        let setup = self.setup;
         let total_hits = self.state.total_hits();
 
         if total_hits == 0 {
-            return OsuPerformanceAttributes {
-                difficulty: self.attrs,
+            return OsuPerformanceBreakdown {
+                attributes: OsuPerformanceAttributes {
+                    difficulty: setup.attrs.clone(),
+                    ..Default::default()
+                },
                 ..Default::default()
             };
         }
 
-        let mut multiplier = PERFORMANCE_BASE_MULTIPLIER;
+        let hit_error = setup.hit_error.or_else(|| {
+            Self::calculate_normalized_hit_error(
+                setup.hit_window,
+                total_hits,
+                setup.accuracy_hit_objects_count,
+                self.state.n300,
+            )
+        });
 
-        self.effective_miss_count = self.state.misses.into();
-
-        // Calculate accuracy hit objects count
-        let mut accuracy_hit_objects_count = self.attrs.n_circles;
-        if !self.using_classic_slider_acc {
-            accuracy_hit_objects_count += self.attrs.n_sliders;
-        } else if ENABLE_EFFECTIVE_MISS_COUNT {
-            self.effective_miss_count =
-                self.effective_miss_count
-                    .max(Self::calculate_effective_miss_count(
-                        &self.attrs,
-                        self.state.max_combo,
-                        self.state.misses,
-                        total_hits - self.state.n300,
-                    ));
-        }
-
-        let normalized_hit_error = Self::calculate_normalized_hit_error(
-            self.attrs.od(),
-            total_hits,
-            accuracy_hit_objects_count,
-            self.state.n300,
-        );
+        let normalized_hit_error = hit_error.unwrap_or(200.0 - setup.attrs.od() * 10.0);
 
         let total_hits = f64::from(total_hits);
 
-        if self.mods.nf() {
-            multiplier *= (1.0 - 0.02 * f64::from(self.state.misses)).max(0.9);
-        }
+        let no_fail_multiplier = if setup.mods.nf() {
+            (1.0 - 0.02 * f64::from(self.state.misses)).max(0.9)
+        } else {
+            1.0
+        };
 
-        if self.mods.so() && total_hits > 0.0 {
-            multiplier *= 1.0 - (f64::from(self.attrs.n_spinners) / total_hits).powf(0.85);
-        }
+        let spun_out_multiplier = if setup.mods.so() && total_hits > 0.0 {
+            1.0 - (f64::from(setup.attrs.n_spinners) / total_hits).powf(0.85)
+        } else {
+            1.0
+        };
 
-        // 保留rx的计算
-        if self.mods.rx() {
-            let od = self.attrs.od();
+        let multiplier = PERFORMANCE_BASE_MULTIPLIER * no_fail_multiplier * spun_out_multiplier;
 
-            // * https://www.desmos.com/calculator/bc9eybdthb
-            // * we use OD13.3 as maximum since it's the value at which great hitwidow becomes 0
-            // * this is well beyond currently maximum achievable OD which is 12.17 (DTx2 + DA with OD11)
-            let (n100_mult, n50_mult) = if od > 0.0 {
-                (
-                    (1.0 - (od / 13.33).powf(1.8)).max(0.0),
-                    (1.0 - (od / 13.33).powf(5.0)).max(0.0),
-                )
-            } else {
-                (1.0, 1.0)
-            };
+        let effective_miss_count = self.effective_miss_count;
 
+        if let Some((n100_mult, n50_mult)) = setup.relax_hit_multipliers {
             // * As we're adding Oks and Mehs to an approximated number of combo breaks the result can be
             // * higher than total hits in specific scenarios (which breaks some calculations) so we need to clamp it.
             self.effective_miss_count = (self.effective_miss_count
@@ -124,75 +190,170 @@ impl OsuPerformanceCalculator<'_> {
         }
 
         // Calculate weights
-        let aim_weight = self.calculate_aim_weight(normalized_hit_error, total_hits);
-        let speed_weight = self.calculate_speed_weight(normalized_hit_error);
-        let accuracy_weight = self.calculate_accuracy_weight(accuracy_hit_objects_count);
+        let (aim_accuracy_weight, aim_combo_weight, flashlight_length_weight) =
+            self.calculate_aim_weights(normalized_hit_error, total_hits);
+        let aim_weight = aim_accuracy_weight * aim_combo_weight;
+        let (speed_accuracy_weight, speed_combo_weight) =
+            self.calculate_speed_weights(normalized_hit_error);
+        let speed_weight = speed_accuracy_weight * speed_combo_weight;
+        let accuracy_length_weight = setup.accuracy_length_weight;
+        let accuracy_mod_weight = setup.accuracy_mod_weight;
 
         // Calculate skill values
-        let aim_value = aim_weight
-            * Self::calculate_skill_value(self.attrs.aim)
-            * self.calculate_miss_weight(self.attrs.aim_difficult_strain_count);
-        let jump_aim_value = aim_weight
-            * Self::calculate_skill_value(self.attrs.jump)
-            * self.calculate_miss_weight(self.attrs.jump_aim_difficult_strain_count);
-        let flow_aim_value = aim_weight
-            * Self::calculate_skill_value(self.attrs.flow)
-            * self.calculate_miss_weight(self.attrs.flow_aim_difficult_strain_count);
-        let precision_value = aim_weight
-            * Self::calculate_skill_value(self.attrs.precision)
-            * self.calculate_miss_weight(self.attrs.aim_difficult_strain_count);
-
-        let speed_value = speed_weight
-            * Self::calculate_skill_value(self.attrs.speed)
-            * self.calculate_miss_weight(self.attrs.speed_difficult_strain_count);
-        let stamina_value = speed_weight
-            * Self::calculate_skill_value(self.attrs.stamina)
-            * self.calculate_miss_weight(self.attrs.stamina_difficult_strain_count);
-
-        let accuracy_value = Self::calculate_accuracy_value(normalized_hit_error)
-            * self.attrs.accuracy
-            * accuracy_weight;
+        let aim = self.skill_breakdown(
+            setup.attrs.aim,
+            setup.attrs.aim_difficult_strain_count,
+            |misses| misses.aim,
+        );
+        let jump_aim = self.skill_breakdown(
+            setup.attrs.jump,
+            setup.attrs.jump_aim_difficult_strain_count,
+            |misses| misses.jump_aim,
+        );
+        let flow_aim = self.skill_breakdown(
+            setup.attrs.flow,
+            setup.attrs.flow_aim_difficult_strain_count,
+            |misses| misses.flow_aim,
+        );
+        let precision = self.skill_breakdown(
+            setup.attrs.precision,
+            setup.attrs.precision_difficult_strain_count,
+            |misses| misses.precision,
+        );
+        // Reading strains add onto aim strains so reading shares the
+        // difficult strain count of aim and its base value is what it adds
+        // onto the aim base value
+        let reading = OsuSkillBreakdown {
+            base_value: Self::calculate_skill_value(setup.attrs.aim.hypot(setup.attrs.reading))
+                - aim.base_value,
+            ..self.skill_breakdown(
+                setup.attrs.reading,
+                setup.attrs.aim_difficult_strain_count,
+                |misses| misses.reading,
+            )
+        };
+        let speed = self.skill_breakdown(
+            setup.attrs.speed,
+            setup.attrs.speed_difficult_strain_count,
+            |misses| misses.speed,
+        );
+        let stamina = self.skill_breakdown(
+            setup.attrs.stamina,
+            setup.attrs.stamina_difficult_strain_count,
+            |misses| misses.stamina,
+        );
+        let flashlight = self.skill_breakdown(
+            setup.attrs.flashlight,
+            setup.attrs.flashlight_difficult_strain_count,
+            |misses| misses.flashlight,
+        );
+
+        let slider_nerf_factor = self.calculate_slider_nerf_factor();
+        let aim_value = aim_weight * aim.base_value * aim.miss_weight * slider_nerf_factor;
+        let jump_aim_value =
+            aim_weight * jump_aim.base_value * jump_aim.miss_weight * slider_nerf_factor;
+        let flow_aim_value =
+            aim_weight * flow_aim.base_value * flow_aim.miss_weight * slider_nerf_factor;
+        let precision_value =
+            aim_weight * precision.base_value * precision.miss_weight * slider_nerf_factor;
+        // Reading approach circles doesn't depend on following sliders
+        let reading_value = aim_weight * reading.base_value * reading.miss_weight;
+
+        let speed_value = speed_weight * speed.base_value * speed.miss_weight;
+        let stamina_value = speed_weight * stamina.base_value * stamina.miss_weight;
+
+        let flashlight_value = if setup.mods.fl() {
+            aim_weight * flashlight_length_weight * flashlight.base_value * flashlight.miss_weight
+        } else {
+            0.0
+        };
+
+        let accuracy_base_value =
+            Self::calculate_accuracy_value(normalized_hit_error) * setup.attrs.accuracy;
+        let accuracy_value = accuracy_base_value * (accuracy_length_weight * accuracy_mod_weight);
 
         // Apply length bonus
-        let (mut final_aim, mut final_jump_aim, mut final_flow_aim, mut final_precision) =
-            (aim_value, jump_aim_value, flow_aim_value, precision_value);
-        let mut final_speed = speed_value;
-        let final_stamina = stamina_value; // Stamina doesn't get length bonus
+        let length_bonus = if setup.config.length_bonus {
+            setup.config.calculate_length_bonus(total_hits)
+        } else {
+            1.0
+        };
 
-        if ENABLE_LENGTH_BONUS {
-            let length_bonus = 0.95
-                + 0.4 * (total_hits / 2000.0).min(1.0)
-                + if total_hits > 2000.0 {
-                    (total_hits / 2000.0).log10() * 0.5
-                } else {
-                    0.0
-                };
-
-            final_aim *= length_bonus;
-            final_jump_aim *= length_bonus;
-            final_flow_aim *= length_bonus;
-            final_precision *= length_bonus;
-            final_speed *= length_bonus;
-        }
+        let final_aim = aim_value * length_bonus;
+        let final_jump_aim = jump_aim_value * length_bonus;
+        let final_flow_aim = flow_aim_value * length_bonus;
+        let final_precision = precision_value * length_bonus;
+        let final_reading = reading_value * length_bonus;
+        let final_speed = speed_value * length_bonus;
+        let final_stamina = stamina_value; // Stamina doesn't get length bonus
+        let final_flashlight = flashlight_value; // Flashlight uses its own length weight
 
         // Calculate total value
         let total_value = (final_aim.powf(1.1)
+            + final_reading.powf(1.1)
             + final_speed.max(final_stamina).powf(1.1)
+            + final_flashlight.powf(1.1)
             + accuracy_value.powf(1.1))
         .powf(1.0 / 1.1)
             * multiplier;
 
-        OsuPerformanceAttributes {
-            difficulty: self.attrs,
+        let attributes = OsuPerformanceAttributes {
+            difficulty: setup.attrs.clone(),
             pp: total_value,
             pp_aim: final_aim,
             pp_jump_aim: final_jump_aim,
             pp_flow_aim: final_flow_aim,
             pp_precision: final_precision,
+            pp_reading: final_reading,
             pp_speed: final_speed,
             pp_stamina: final_stamina,
+            pp_flashlight: final_flashlight,
             pp_acc: accuracy_value,
             effective_miss_count: self.effective_miss_count,
+            estimated_unstable_rate: hit_error.map(|hit_error| hit_error * 10.0),
+        };
+
+        OsuPerformanceBreakdown {
+            attributes,
+            base_multiplier: PERFORMANCE_BASE_MULTIPLIER,
+            no_fail_multiplier,
+            spun_out_multiplier,
+            effective_miss_count,
+            relax_miss_adjustment: self.effective_miss_count - effective_miss_count,
+            normalized_hit_error,
+            aim_accuracy_weight,
+            aim_combo_weight,
+            flashlight_length_weight,
+            slider_nerf_factor,
+            speed_accuracy_weight,
+            speed_combo_weight,
+            length_bonus,
+            aim,
+            jump_aim,
+            flow_aim,
+            precision,
+            reading,
+            speed,
+            stamina,
+            flashlight,
+            accuracy_base_value,
+            accuracy_length_weight,
+            accuracy_mod_weight,
+        }
+    }
+
+    fn skill_breakdown(
+        &self,
+        skill_diff: f64,
+        difficult_strain_count: f64,
+        weighted_misses: impl FnOnce(&PositionalMisses) -> f64,
+    ) -> OsuSkillBreakdown {
+        let miss_count = self.skill_miss_count(weighted_misses);
+
+        OsuSkillBreakdown {
+            base_value: Self::calculate_skill_value(skill_diff),
+            miss_count,
+            miss_weight: self.calculate_miss_weight(difficult_strain_count, miss_count),
         }
     }
 
@@ -201,16 +362,16 @@ impl OsuPerformanceCalculator<'_> {
     }
 
     fn calculate_normalized_hit_error(
-        od: f64,
+        hit_window: f64,
         object_count: u32,
         accuracy_object_count: u32,
         count300: u32,
-    ) -> f64 {
+    ) -> Option<f64> {
         let relevant_300_count =
             count300 as i32 - (object_count as i32 - accuracy_object_count as i32);
 
         if relevant_300_count <= 0 {
-            return 200.0 - od * 10.0;
+            return None;
         }
 
         // Probability of landing a 300 where the player has a 20% chance of getting at least the given amount of 30
@@ -219,121 +380,113 @@ impl OsuPerformanceCalculator<'_> {
             1.0 + f64::from(accuracy_object_count) - f64::from(relevant_300_count),
         );
 
-        let probability = match beta_result {
-            Ok(beta) => beta.inverse_cdf(0.2),
-            Err(_) => return 200.0 - od * 10.0, // 如果 Beta 分布创建失败，返回默认值
-        };
+        let probability = beta_result.ok()?.inverse_cdf(0.2);
 
         // Add the left tail of the normal distribution.
         let probability = probability + (1.0 - probability) / 2.0;
         // The value on the x-axis for the given probability.
         let normal_result = Normal::new(0.0, 1.0);
-        let z_value = match normal_result {
-            Ok(normal) => normal.inverse_cdf(probability),
-            Err(_) => return 200.0 - od * 10.0, // 如果 Normal 分布创建失败，返回默认值
-        };
+        let z_value = normal_result.ok()?.inverse_cdf(probability);
 
-        let hit_window = 79.5 - od * 6.0;
-        hit_window / z_value // Hit errors are normally distributed along the x-axis.
+        Some(hit_window / z_value) // Hit errors are normally distributed along the x-axis.
     }
 
-    fn calculate_miss_weight(&self, difficult_strain_count: f64) -> f64 {
-        if ENABLE_CSR {
+    /// The effective miss count of a skill, considering the position of
+    /// misses if available.
+    fn skill_miss_count(&self, weighted: impl FnOnce(&PositionalMisses) -> f64) -> f64 {
+        match self.setup.positional_misses {
+            Some(ref misses) => misses.miss_count(self.effective_miss_count, weighted(misses)),
+            None => self.effective_miss_count,
+        }
+    }
+
+    fn calculate_miss_weight(&self, difficult_strain_count: f64, miss_count: f64) -> f64 {
+        if self.setup.config.combo_scaling_rework {
+            let miss_weight_base = self.setup.config.miss_weight_base;
+
             if difficult_strain_count <= 1.0 {
                 // 当 difficult_strain_count <= 1 时，使用简化计算避免 ln() 问题
-                return 0.96 / (self.effective_miss_count / 4.0 + 1.0);
+                return miss_weight_base / (miss_count / 4.0 + 1.0);
             }
 
             let ln_value = difficult_strain_count.ln();
-            let powered_ln = ln_value.powf(0.94);
+            let powered_ln = ln_value.powf(self.setup.config.csr_exponent);
 
             // 检查是否产生了无效值
             if powered_ln.is_finite() && powered_ln > 0.0 {
-                0.96 / ((self.effective_miss_count / (4.0 * powered_ln)) + 1.0)
+                miss_weight_base / ((miss_count / (4.0 * powered_ln)) + 1.0)
             } else {
                 // 回退到简化计算
-                0.96 / (self.effective_miss_count / 4.0 + 1.0)
+                miss_weight_base / (miss_count / 4.0 + 1.0)
             }
         } else {
-            0.97_f64.powf(self.effective_miss_count)
+            0.97_f64.powf(miss_count)
         }
     }
 
-    fn calculate_aim_weight(&self, normalized_hit_error: f64, total_hits: f64) -> f64 {
+    fn calculate_slider_nerf_factor(&self) -> f64 {
+        // * In classic scores we can't know whether sliders were followed
+        // * properly; slider breaks are covered by the effective miss count.
+        if self.setup.using_classic_slider_acc || self.setup.attrs.aim_difficult_slider_count <= 0.0
+        {
+            return 1.0;
+        }
+
+        // * We add tick misses here since they too mean that the player didn't follow the slider properly
+        // * We however aren't adding misses here because missing slider heads has a harsh penalty by itself and doesn't mean that the rest of the slider wasn't followed properly
+        let estimate_improperly_followed_difficult_sliders = f64::from(
+            n_slider_ends_dropped(&self.setup.attrs, &self.state)
+                + n_large_tick_miss(&self.setup.attrs, &self.state),
+        )
+        .clamp(0.0, self.setup.attrs.aim_difficult_slider_count);
+
+        (1.0 - SLIDER_NERF_MIN_FACTOR)
+            * (1.0
+                - estimate_improperly_followed_difficult_sliders
+                    / self.setup.attrs.aim_difficult_slider_count)
+                .powf(3.0)
+            + SLIDER_NERF_MIN_FACTOR
+    }
+
+    fn calculate_aim_weights(&self, normalized_hit_error: f64, total_hits: f64) -> (f64, f64, f64) {
         let accuracy_weight = 0.995_f64.powf(normalized_hit_error) * 1.04;
-        let combo_weight = if ENABLE_CSR {
+        let combo_weight = if self.setup.config.combo_scaling_rework {
             1.0
         } else {
-            if self.attrs.max_combo == 0 {
+            if self.setup.attrs.max_combo == 0 {
                 1.0
             } else {
                 (f64::from(self.state.max_combo).powf(0.8))
-                    / (f64::from(self.attrs.max_combo).powf(0.8))
+                    / (f64::from(self.setup.attrs.max_combo).powf(0.8))
             }
         };
 
-        let flashlight_length_weight = if self.mods.fl() {
+        let flashlight_length_weight = if self.setup.mods.fl() {
             1.0 + combo_weight * (total_hits / 2000.0).atan()
         } else {
             1.0
         };
 
-        accuracy_weight * combo_weight * flashlight_length_weight
+        (accuracy_weight, combo_weight, flashlight_length_weight)
     }
 
-    fn calculate_speed_weight(&self, normalized_hit_error: f64) -> f64 {
+    fn calculate_speed_weights(&self, normalized_hit_error: f64) -> (f64, f64) {
         let accuracy_weight = 0.985_f64.powf(normalized_hit_error) * 1.12;
-        let combo_weight = if ENABLE_CSR {
+        let combo_weight = if self.setup.config.combo_scaling_rework {
             1.0
         } else {
-            if self.attrs.max_combo == 0 {
+            if self.setup.attrs.max_combo == 0 {
                 1.0
             } else {
                 (f64::from(self.state.max_combo).powf(0.4))
-                    / (f64::from(self.attrs.max_combo).powf(0.4))
+                    / (f64::from(self.setup.attrs.max_combo).powf(0.4))
             }
         };
 
-        accuracy_weight * combo_weight
-    }
-
-    fn calculate_accuracy_weight(&self, accuracy_hit_objects_count: u32) -> f64 {
-        let length_weight = (f64::from(accuracy_hit_objects_count + 400) / 1050.0).tanh() * 1.2;
-
-        let mut mod_weight = 1.0;
-        if self.mods.hd() {
-            mod_weight *= 1.02;
-        }
-        if self.mods.fl() {
-            mod_weight *= 1.04;
-        }
-
-        length_weight * mod_weight
+        (accuracy_weight, combo_weight)
     }
 
     fn calculate_accuracy_value(normalized_hit_error: f64) -> f64 {
         560.0 * 0.85_f64.powf(normalized_hit_error)
     }
-
-    fn calculate_effective_miss_count(
-        attributes: &OsuDifficultyAttributes,
-        score_max_combo: u32,
-        count_miss: u32,
-        count_mistakes: u32,
-    ) -> f64 {
-        let mut combo_based_miss_count = 0.0;
-
-        if attributes.n_sliders > 0 {
-            let full_combo_threshold =
-                f64::from(attributes.max_combo) - 0.1 * f64::from(attributes.n_sliders);
-            if f64::from(score_max_combo) < full_combo_threshold {
-                combo_based_miss_count = full_combo_threshold / f64::from(score_max_combo).max(1.0);
-            }
-        }
-
-        // Clamp miss count to maximum amount of possible breaks
-        combo_based_miss_count = combo_based_miss_count.min(f64::from(count_mistakes));
-
-        f64::from(count_miss).max(combo_based_miss_count)
-    }
 }
@@ -1,4 +1,16 @@
-use crate::{model::mode::ConvertError, osu::OsuGradualDifficulty, Beatmap, Difficulty};
+use std::cmp;
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::{performance::projection, Projection},
+    model::{
+        mode::ConvertError,
+        snapshot::{self, SnapshotError, SnapshotReader, SnapshotState, SnapshotWriter},
+    },
+    osu::{object::OsuObjectKind, HitResult, OsuDifficultyAttributes, OsuGradualDifficulty},
+    Beatmap, Difficulty,
+};
 
 use super::{OsuPerformanceAttributes, OsuScoreState};
 
@@ -11,6 +23,9 @@ use super::{OsuPerformanceAttributes, OsuScoreState};
 /// Both methods require an [`OsuScoreState`] that contains the current
 /// hitresults as well as the maximum combo so far.
 ///
+/// Alternatively, [`judge`] takes individual judgements and keeps track of
+/// the score state itself. The two approaches should not be mixed.
+///
 /// If you only want to calculate difficulty attributes use
 /// [`OsuGradualDifficulty`] instead.
 ///
@@ -74,18 +89,44 @@ use super::{OsuPerformanceAttributes, OsuScoreState};
 ///
 /// [`next`]: OsuGradualPerformance::next
 /// [`nth`]: OsuGradualPerformance::nth
+/// [`judge`]: OsuGradualPerformance::judge
 pub struct OsuGradualPerformance {
     lazer: bool,
     difficulty: OsuGradualDifficulty,
+    judged: Judged,
+}
+
+/// Score state that is tracked through [`OsuGradualPerformance::judge`].
+#[derive(Default)]
+struct Judged {
+    state: OsuScoreState,
+    combo: u32,
+    attrs: Option<OsuDifficultyAttributes>,
+    remaining_ticks: usize,
+    remaining_ends: usize,
+    is_spinner: bool,
 }
 
+snapshot_state!(Judged {
+    state,
+    combo,
+    attrs,
+    remaining_ticks,
+    remaining_ends,
+    is_spinner,
+});
+
 impl OsuGradualPerformance {
     /// Create a new gradual performance calculator for osu!standard maps.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
         let lazer = difficulty.get_lazer();
         let difficulty = OsuGradualDifficulty::new(difficulty, map)?;
 
-        Ok(Self { lazer, difficulty })
+        Ok(Self {
+            lazer,
+            difficulty,
+            judged: Judged::default(),
+        })
     }
 
     /// Process the next hit object and calculate the performance attributes
@@ -105,20 +146,248 @@ impl OsuGradualPerformance {
     ///
     /// Note that the count is zero-indexed, so `n=0` will process 1 object,
     /// `n=1` will process 2, and so on.
-    #[allow(clippy::missing_panics_doc)]
     pub fn nth(&mut self, state: OsuScoreState, n: usize) -> Option<OsuPerformanceAttributes> {
-        let performance = self
+        let attrs = self.difficulty.nth(n)?;
+
+        Some(self.calculate(attrs, state, self.difficulty.idx))
+    }
+
+    /// Index of the first hit object that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// See [`OsuGradualDifficulty::idx_at_time`].
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.difficulty.idx_at_time(time)
+    }
+
+    /// Process all remaining hit objects that have started at the given
+    /// playback time in milliseconds.
+    ///
+    /// Returns the amount of newly processed hit objects.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        self.difficulty.advance_to_time(time)
+    }
+
+    /// Process all remaining hit objects that have started at the given
+    /// playback time in milliseconds and calculate the performance attributes
+    /// for the resulting score state.
+    ///
+    /// Returns `None` if no hit object has started yet.
+    pub fn attributes_at_time(
+        &mut self,
+        state: OsuScoreState,
+        time: f64,
+    ) -> Option<OsuPerformanceAttributes> {
+        let attrs = self.difficulty.attributes_at_time(time)?;
+
+        Some(self.calculate(attrs, state, self.difficulty.idx))
+    }
+
+    /// Process a single judgement and calculate the performance attributes
+    /// for the resulting score state.
+    ///
+    /// Judgements must be provided in order. A judgement for a hit object,
+    /// i.e. [`HitResult::Great`], [`HitResult::Ok`], [`HitResult::Meh`], or
+    /// [`HitResult::Miss`], processes the next hit object. It may be followed
+    /// by judgements for the nested objects of that hit object, i.e. slider
+    /// ticks, repeats, and slider ends, or bonus spins for spinners.
+    ///
+    /// The combo, as well as tick and slider end hits, are tracked
+    /// internally.
+    ///
+    /// Returns `None` if there is no hit object left or if the judgement does
+    /// not fit the current hit object, e.g. a slider tick on a circle or more
+    /// slider ticks than the slider has.
+    pub fn judge(&mut self, result: HitResult) -> Option<OsuPerformanceAttributes> {
+        let classic = self
+            .difficulty
+            .difficulty
+            .get_mods()
+            .no_slider_head_acc(self.lazer);
+
+        let judged = &mut self.judged;
+
+        match result {
+            HitResult::Great | HitResult::Ok | HitResult::Meh | HitResult::Miss => {
+                let attrs = self.difficulty.next()?;
+                let h = self.difficulty.last_object()?;
+
+                let is_slider = if let OsuObjectKind::Slider(ref slider) = h.kind {
+                    judged.remaining_ticks = slider.large_tick_count();
+                    judged.remaining_ends = usize::from(slider.tail().is_some());
+
+                    true
+                } else {
+                    judged.remaining_ticks = 0;
+                    judged.remaining_ends = 0;
+
+                    false
+                };
+
+                judged.is_spinner = h.is_spinner();
+                judged.attrs = Some(attrs);
+
+                match result {
+                    HitResult::Great => judged.state.n300 += 1,
+                    HitResult::Ok => judged.state.n100 += 1,
+                    HitResult::Meh => judged.state.n50 += 1,
+                    _ => judged.state.misses += 1,
+                }
+
+                // Slider heads are large ticks without slider accuracy
+                if classic && is_slider && result.is_hit() {
+                    judged.state.large_tick_hits += 1;
+                }
+            }
+            HitResult::SliderTickHit | HitResult::SliderTickMiss => {
+                judged.remaining_ticks = judged.remaining_ticks.checked_sub(1)?;
+
+                if result.is_hit() {
+                    judged.state.large_tick_hits += 1;
+                }
+            }
+            HitResult::SliderEndHit | HitResult::SliderEndMiss => {
+                judged.remaining_ends = judged.remaining_ends.checked_sub(1)?;
+
+                match (result.is_hit(), classic) {
+                    (true, true) => judged.state.small_tick_hits += 1,
+                    (true, false) => judged.state.slider_end_hits += 1,
+                    (false, _) => {}
+                }
+            }
+            HitResult::SpinnerBonus if judged.is_spinner => {}
+            HitResult::SpinnerBonus => return None,
+        }
+
+        match result {
+            // Missing a slider end does not break combo
+            HitResult::SliderEndMiss | HitResult::SpinnerBonus => {}
+            HitResult::Miss | HitResult::SliderTickMiss => judged.combo = 0,
+            _ => {
+                judged.combo += 1;
+                judged.state.max_combo = cmp::max(judged.state.max_combo, judged.combo);
+            }
+        }
+
+        let attrs = judged.attrs.clone()?;
+        let state = judged.state.clone();
+
+        Some(self.calculate(attrs, state, self.difficulty.idx))
+    }
+
+    /// The score state resulting from all judgements passed to
+    /// [`OsuGradualPerformance::judge`] so far.
+    pub const fn judged_state(&self) -> &OsuScoreState {
+        &self.judged.state
+    }
+
+    /// Project the final score when hitting all remaining hit objects without
+    /// breaking combo while keeping the current ratio of 300s, 100s, and 50s.
+    ///
+    /// The `state` must contain the hitresults of all hit objects that were
+    /// processed so far. The processing state remains unchanged.
+    ///
+    /// Score states don't carry the current combo so after a combo break the
+    /// current combo is assumed to be `0`. The projected max combo is then
+    /// the larger of the state's max combo and the combo of all remaining
+    /// hit objects. Combo that was rebuilt since the last break is not accounted
+    /// for so the projected max combo may be too low in that case.
+    ///
+    /// Returns `None` if the map has no hit objects.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn projection(
+        &mut self,
+        state: OsuScoreState,
+    ) -> Option<Projection<OsuScoreState, OsuPerformanceAttributes>> {
+        let final_attrs = self.difficulty.final_attributes()?;
+        let curr_attrs = self.difficulty.current().unwrap_or_default();
+
+        let classic = self
+            .difficulty
             .difficulty
-            .nth(n)?
+            .get_mods()
+            .no_slider_head_acc(self.lazer);
+
+        let remaining = final_attrs.n_objects() - curr_attrs.n_objects();
+        let [n300, n100, n50] =
+            projection::distribute(remaining, [state.n300, state.n100, state.n50]);
+        let new_ticks = final_attrs.n_large_ticks - curr_attrs.n_large_ticks;
+        let new_sliders = final_attrs.n_sliders - curr_attrs.n_sliders;
+
+        let mut projected = OsuScoreState {
+            max_combo: projection::full_combo(
+                state.max_combo,
+                curr_attrs.max_combo,
+                final_attrs.max_combo,
+            ),
+            large_tick_hits: state.large_tick_hits + new_ticks,
+            small_tick_hits: state.small_tick_hits,
+            slider_end_hits: state.slider_end_hits,
+            n300: state.n300 + n300,
+            n100: state.n100 + n100,
+            n50: state.n50 + n50,
+            misses: state.misses,
+        };
+
+        // Slider heads are large ticks without slider accuracy
+        if classic {
+            projected.large_tick_hits += new_sliders;
+            projected.small_tick_hits += new_sliders;
+        } else {
+            projected.slider_end_hits += new_sliders;
+        }
+
+        let n_objects = final_attrs.n_objects() as usize;
+        let attributes = self.calculate(final_attrs, projected.clone(), n_objects);
+
+        Some(Projection {
+            state: projected,
+            attributes,
+        })
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// Next to the difficulty state, see
+    /// [`OsuGradualDifficulty::to_snapshot`], the snapshot contains the
+    /// score state of all judgements so far.
+    ///
+    /// Use [`OsuGradualPerformance::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        snapshot::to_snapshot(self, GameMode::Osu, self.difficulty.checksum())
+    }
+
+    /// Restore a snapshot of [`OsuGradualPerformance::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let mut gradual = Self::new(difficulty, map)?;
+        let checksum = gradual.difficulty.checksum();
+        snapshot::from_snapshot(&mut gradual, GameMode::Osu, checksum, snapshot)?;
+
+        Ok(gradual)
+    }
+
+    fn calculate(
+        &self,
+        attrs: OsuDifficultyAttributes,
+        state: OsuScoreState,
+        passed_objects: usize,
+    ) -> OsuPerformanceAttributes {
+        attrs
             .performance()
             .lazer(self.lazer)
             .state(state)
             .difficulty(self.difficulty.difficulty.clone())
-            .passed_objects(self.difficulty.idx as u32)
+            .passed_objects(passed_objects as u32)
             .calculate()
-            .expect("no conversion required");
-
-        Some(performance)
+            .expect("no conversion required")
     }
 
     /// Returns the amount of remaining objects.
@@ -128,12 +397,85 @@ impl OsuGradualPerformance {
     }
 }
 
+impl SnapshotState for OsuGradualPerformance {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        self.difficulty.write(writer);
+        self.judged.write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        self.difficulty.read(reader)?;
+        self.judged.read(reader)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use rosu_mods::{GameModIntermode, GameModsIntermode};
+
     use crate::{osu::OsuPerformance, Beatmap};
 
     use super::*;
 
+    /// Judge each object with `result` and hit all nested objects.
+    fn judge_all(
+        gradual: &mut OsuGradualPerformance,
+        result: HitResult,
+    ) -> OsuPerformanceAttributes {
+        let mut last = None;
+
+        while let Some(attrs) = gradual.judge(result) {
+            last = Some(attrs);
+
+            let (n_ticks, has_end) = match gradual.difficulty.last_object().map(|h| &h.kind) {
+                Some(OsuObjectKind::Slider(slider)) => {
+                    (slider.large_tick_count(), slider.tail().is_some())
+                }
+                _ => (0, false),
+            };
+
+            for _ in 0..n_ticks {
+                last = gradual.judge(HitResult::SliderTickHit);
+            }
+
+            if has_end {
+                last = gradual.judge(HitResult::SliderEndHit);
+            }
+        }
+
+        last.unwrap()
+    }
+
+    #[test]
+    fn time() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = OsuGradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        let n = gradual.advance_to_time(20_000.0);
+        assert_eq!(n, gradual.idx_at_time(20_000.0));
+        assert_eq!(gradual.advance_to_time(10_000.0), 0);
+
+        let state = OsuScoreState {
+            max_combo: n as u32 - 1,
+            n300: n as u32 - 1,
+            misses: 1,
+            ..Default::default()
+        };
+
+        let attrs = gradual.attributes_at_time(state.clone(), 20_000.0).unwrap();
+
+        let expected = OsuPerformance::new(&map)
+            .difficulty(difficulty)
+            .passed_objects(n as u32)
+            .state(state)
+            .calculate()
+            .unwrap();
+
+        assert_eq!(attrs, expected);
+    }
+
     #[test]
     fn next_and_nth() {
         let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
@@ -181,4 +523,126 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn judge_perfect() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut classic = GameModsIntermode::new();
+        classic.insert(GameModIntermode::Classic);
+
+        let difficulties = [
+            Difficulty::new(),
+            Difficulty::new().lazer(false),
+            Difficulty::new().mods(classic),
+        ];
+
+        for difficulty in difficulties {
+            let mut gradual = OsuGradualPerformance::new(difficulty.clone(), &map).unwrap();
+            let attrs = judge_all(&mut gradual, HitResult::Great);
+
+            let mut regular_calc = OsuPerformance::new(&map).difficulty(difficulty);
+            let expected_state = regular_calc.generate_state().unwrap();
+
+            let judged = gradual.judged_state();
+            assert_eq!(judged.max_combo, expected_state.max_combo);
+            assert_eq!(judged.n300, expected_state.n300);
+            assert_eq!(judged.misses, 0);
+
+            assert_eq!(attrs, regular_calc.calculate().unwrap());
+        }
+    }
+
+    #[test]
+    fn judge_combo() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut gradual = OsuGradualPerformance::new(Difficulty::new(), &map).unwrap();
+
+        // Advance to the first slider with a tick, ignoring nested objects
+        loop {
+            gradual.judge(HitResult::Great).unwrap();
+
+            if let Some(OsuObjectKind::Slider(slider)) =
+                gradual.difficulty.last_object().map(|h| &h.kind)
+            {
+                if slider.large_tick_count() > 0 {
+                    break;
+                }
+            }
+        }
+
+        let before = gradual.judged_state().clone();
+
+        gradual.judge(HitResult::SliderTickMiss).unwrap();
+        assert_eq!(gradual.judged.combo, 0);
+
+        // Slider ticks are exhausted or a slider end follows
+        while gradual.judge(HitResult::SliderTickHit).is_some() {}
+        gradual.judge(HitResult::SliderEndMiss).unwrap();
+        assert!(gradual.judge(HitResult::SliderEndHit).is_none());
+        assert!(gradual.judge(HitResult::SpinnerBonus).is_none());
+
+        let state = gradual.judged_state();
+        assert_eq!(state.max_combo, before.max_combo);
+        assert_eq!(state.slider_end_hits, before.slider_end_hits);
+
+        gradual.judge(HitResult::Miss).unwrap();
+        assert_eq!(gradual.judged.combo, 0);
+        assert_eq!(gradual.judged_state().misses, 1);
+    }
+
+    #[test]
+    fn snapshot() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(72); // HDDT
+        let mut gradual = OsuGradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        for _ in 0..100 {
+            gradual.judge(HitResult::Great).unwrap();
+        }
+
+        gradual.judge(HitResult::Miss).unwrap();
+
+        let snapshot = gradual.to_snapshot();
+        let mut restored =
+            OsuGradualPerformance::from_snapshot(difficulty, &map, &snapshot).unwrap();
+
+        assert_eq!(restored.judged_state(), gradual.judged_state());
+        assert_eq!(restored.judged.combo, 0);
+        assert_eq!(
+            judge_all(&mut restored, HitResult::Great),
+            judge_all(&mut gradual, HitResult::Great)
+        );
+        assert_eq!(restored.judged_state(), gradual.judged_state());
+    }
+
+    #[test]
+    fn projection() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(72); // HDDT
+        let mut gradual = OsuGradualPerformance::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_2nd = OsuGradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        let state = OsuPerformance::new(&map)
+            .difficulty(difficulty.clone())
+            .passed_objects(100)
+            .generate_state()
+            .unwrap();
+
+        gradual.nth(state.clone(), 99).unwrap();
+        let projection = gradual.projection(state.clone()).unwrap();
+
+        let mut regular_calc = OsuPerformance::new(&map).difficulty(difficulty);
+        assert_eq!(projection.state, regular_calc.generate_state().unwrap());
+        assert_eq!(projection.attributes, regular_calc.calculate().unwrap());
+
+        // The processing state is unaffected
+        assert_eq!(
+            gradual.next(state.clone()),
+            gradual_2nd.nth(state.clone(), 100)
+        );
+    }
 }
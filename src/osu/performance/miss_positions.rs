@@ -0,0 +1,150 @@
+use crate::{
+    any::difficulty::skills::{top_strain_weight, StrainSkill},
+    osu::difficulty::skills::{aim::Aim, OsuSkills},
+    util::float_ext::FloatExt,
+};
+
+/// Misses of known position, weighted by the strain of each skill at the
+/// missed objects.
+///
+/// A miss at the highest strain of a skill counts as one full miss while
+/// misses in easier sections count proportionally less.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub(crate) struct PositionalMisses {
+    /// Amount of misses whose position is known.
+    pub n_positioned: f64,
+    pub aim: f64,
+    pub jump_aim: f64,
+    pub flow_aim: f64,
+    pub precision: f64,
+    pub reading: f64,
+    pub speed: f64,
+    pub stamina: f64,
+    pub flashlight: f64,
+}
+
+impl PositionalMisses {
+    /// `miss_indices` must be sorted and must not contain duplicates.
+    pub fn new(skills: &OsuSkills, miss_indices: &[usize]) -> Self {
+        let OsuSkills {
+            aim:
+                Aim {
+                    all: aim,
+                    raw: raw_aim,
+                    jump: jump_aim,
+                    flow: flow_aim,
+                    precision,
+                    reading,
+                    ..
+                },
+            speed,
+            stamina,
+            rhythm_complexity: _,
+            flashlight,
+        } = skills;
+
+        // The first hit object has no difficulty object and thus no strain
+        let n_objects = aim.object_strains().len() + 1;
+        let miss_indices = &miss_indices[..miss_indices.partition_point(|&idx| idx < n_objects)];
+
+        // Difficulty values are paired up the same way as for the
+        // difficult strain counts
+        Self {
+            n_positioned: miss_indices.len() as f64,
+            aim: weighted_misses(aim, aim.cloned_difficulty_value(), miss_indices),
+            jump_aim: weighted_misses(jump_aim, raw_aim.cloned_difficulty_value(), miss_indices),
+            flow_aim: weighted_misses(flow_aim, flow_aim.cloned_difficulty_value(), miss_indices),
+            precision: weighted_misses(
+                precision,
+                precision.cloned_difficulty_value(),
+                miss_indices,
+            ),
+            reading: weighted_misses(reading, reading.cloned_difficulty_value(), miss_indices),
+            speed: weighted_misses(speed, speed.cloned_difficulty_value(), miss_indices),
+            stamina: weighted_misses(stamina, stamina.cloned_difficulty_value(), miss_indices),
+            flashlight: weighted_misses(
+                flashlight,
+                flashlight.cloned_difficulty_value(),
+                miss_indices,
+            ),
+        }
+    }
+
+    /// Replace the positioned misses within `effective_miss_count` with
+    /// their `weighted` counterpart.
+    pub fn miss_count(&self, effective_miss_count: f64, weighted: f64) -> f64 {
+        (effective_miss_count - self.n_positioned).max(0.0) + weighted
+    }
+}
+
+/// Sum of the weights of all missed objects where the weight of an object is
+/// its contribution to the difficult strain count relative to the hardest
+/// object's contribution.
+fn weighted_misses(skill: &impl StrainSkill, difficulty_value: f64, miss_indices: &[usize]) -> f64 {
+    let object_strains = skill.object_strains();
+
+    // * What would the top strain be if all strain values were identical
+    let consistent_top_strain = difficulty_value / 10.0;
+    let max_strain = object_strains.iter().copied().fold(0.0, f64::max);
+
+    if FloatExt::eq(consistent_top_strain, 0.0) || FloatExt::eq(max_strain, 0.0) {
+        return miss_indices.len() as f64;
+    }
+
+    let max_weight = top_strain_weight(max_strain, consistent_top_strain);
+
+    miss_indices
+        .iter()
+        .map(|&idx| match idx.checked_sub(1) {
+            Some(i) => top_strain_weight(object_strains[i], consistent_top_strain) / max_weight,
+            None => 0.0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        osu::difficulty::{skills::OsuSkills, DifficultyValues},
+        Beatmap, Difficulty,
+    };
+
+    use super::*;
+
+    fn skills() -> OsuSkills {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        DifficultyValues::calculate(&Difficulty::new(), &map).skills
+    }
+
+    #[test]
+    fn weights() {
+        let skills = skills();
+
+        let strains = skills.aim.jump.object_strains();
+        let hardest = strains
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i + 1)
+            .unwrap();
+        let easiest = strains
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i + 1)
+            .unwrap();
+
+        let hard = PositionalMisses::new(&skills, &[hardest]);
+        let easy = PositionalMisses::new(&skills, &[easiest]);
+
+        assert!((hard.n_positioned - 1.0).abs() < f64::EPSILON);
+        assert!((hard.jump_aim - 1.0).abs() < f64::EPSILON);
+        assert!(easy.jump_aim < hard.jump_aim);
+        assert!((hard.miss_count(3.0, hard.jump_aim) - 3.0).abs() < f64::EPSILON);
+
+        let out_of_bounds = PositionalMisses::new(&skills, &[0, 10_000]);
+        assert!((out_of_bounds.n_positioned - 1.0).abs() < f64::EPSILON);
+        assert!(out_of_bounds.aim.abs() < f64::EPSILON);
+    }
+}
@@ -2,11 +2,14 @@ use std::{borrow::Cow, cmp};
 
 use rosu_map::section::general::GameMode;
 
-use self::calculator::OsuPerformanceCalculator;
 pub use self::calculator::PERFORMANCE_BASE_MULTIPLIER;
+use self::{calculator::OsuPerformanceSetup, miss_positions::PositionalMisses};
 
 use crate::{
-    any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance, Performance},
+    any::{
+        performance::solver, Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance,
+        Performance,
+    },
     catch::CatchPerformance,
     mania::ManiaPerformance,
     model::{mode::ConvertError, mods::GameMods},
@@ -16,13 +19,20 @@ use crate::{
 };
 
 use super::{
-    attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes, OsuPerformanceBreakdown},
+    config::{PpPlusConfig, ScoringModel},
+    difficulty::DifficultyValues,
     score_state::{OsuScoreOrigin, OsuScoreState},
+    vanilla::{
+        performance::OsuVanillaPerformanceCalculator, OsuVanillaDifficultyAttributes,
+        OsuVanillaPerformanceAttributes,
+    },
     Osu,
 };
 
 mod calculator;
 pub mod gradual;
+mod miss_positions;
 
 /// Performance calculator on osu!standard maps.
 #[derive(Clone, Debug, PartialEq)]
@@ -39,6 +49,10 @@ pub struct OsuPerformance<'map> {
     pub(crate) n100: Option<u32>,
     pub(crate) n50: Option<u32>,
     pub(crate) misses: Option<u32>,
+    pub(crate) hit_error: Option<f64>,
+    pub(crate) miss_indices: Option<Vec<usize>>,
+    pub(crate) positional_misses: Option<PositionalMisses>,
+    pub(crate) vanilla_attrs: Option<Box<OsuVanillaDifficultyAttributes>>,
     pub(crate) hitresult_priority: HitResultPriority,
 }
 
@@ -169,6 +183,15 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Specify the configuration of the PP+ algorithm.
+    ///
+    /// Defaults to [`PpPlusConfig::DEFAULT`].
+    pub fn pplus_config(mut self, config: PpPlusConfig) -> Self {
+        self.difficulty = self.difficulty.pplus_config(config);
+
+        self
+    }
+
     /// Specify the amount of "large tick" hits.
     ///
     /// The meaning depends on the kind of score:
@@ -237,6 +260,15 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Specify which [`ScoringModel`] [`Performance::calculate`] should use.
+    ///
+    /// [`ScoringModel`]: crate::osu::ScoringModel
+    pub fn scoring_model(mut self, scoring_model: ScoringModel) -> Self {
+        self.difficulty = self.difficulty.scoring_model(scoring_model);
+
+        self
+    }
+
     /// Amount of passed objects for partial plays, e.g. a fail.
     ///
     /// If you want to calculate the performance after every few objects,
@@ -324,6 +356,58 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Specify the unstable rate of the play, i.e. ten times the standard
+    /// deviation of hit errors in milliseconds.
+    ///
+    /// If specified, the unstable rate is used as is instead of being
+    /// estimated through the amount of 300s.
+    ///
+    /// Values that are not finite or not positive are ignored.
+    pub fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        if unstable_rate.is_finite() && unstable_rate > 0.0 {
+            self.hit_error = Some(unstable_rate / 10.0);
+        }
+
+        self
+    }
+
+    /// Specify the standard deviation of hit errors of the play in
+    /// milliseconds.
+    ///
+    /// If specified, the hit error is used as is instead of being estimated
+    /// through the amount of 300s.
+    ///
+    /// Values that are not finite or not positive are ignored.
+    pub fn hit_error(mut self, hit_error: f64) -> Self {
+        if hit_error.is_finite() && hit_error > 0.0 {
+            self.hit_error = Some(hit_error);
+        }
+
+        self
+    }
+
+    /// Specify the indices of the hit objects that were missed, e.g. as
+    /// known from a replay.
+    ///
+    /// Instead of only considering the total amount of misses, each skill
+    /// then weights the misses by its strain at the missed objects so that
+    /// misses in difficult sections are penalized more than misses in easy
+    /// sections.
+    ///
+    /// This requires the strain of each object so it is ignored if the
+    /// calculator was created through difficulty attributes. The misses are
+    /// weighted when the difficulty attributes are calculated so they must
+    /// be specified before [`OsuPerformance::generate_state`] is called.
+    pub fn miss_indices(mut self, miss_indices: &[usize]) -> Self {
+        let mut miss_indices = miss_indices.to_vec();
+        miss_indices.sort_unstable();
+        miss_indices.dedup();
+        self.miss_indices = Some(miss_indices);
+        self.positional_misses = None;
+
+        self
+    }
+
     /// Provide parameters through an [`OsuScoreState`].
     #[allow(clippy::needless_pass_by_value)]
     pub const fn state(mut self, state: OsuScoreState) -> Self {
@@ -361,14 +445,7 @@ impl<'map> OsuPerformance<'map> {
     /// Create the [`OsuScoreState`] that will be used for performance calculation.
     #[allow(clippy::too_many_lines)]
     pub fn generate_state(&mut self) -> Result<OsuScoreState, ConvertError> {
-        let attrs = match self.map_or_attrs {
-            MapOrAttrs::Map(ref map) => {
-                let attrs = self.difficulty.calculate_for_mode::<Osu>(map)?;
-
-                self.map_or_attrs.insert_attrs(attrs)
-            }
-            MapOrAttrs::Attrs(ref attrs) => attrs,
-        };
+        let attrs = self.cache_attributes()?;
 
         let max_combo = attrs.max_combo;
         let n_objects = cmp::min(
@@ -384,47 +461,39 @@ impl<'map> OsuPerformance<'map> {
         let mut n100 = self.n100.map_or(0, |n| cmp::min(n, n_remaining));
         let mut n50 = self.n50.map_or(0, |n| cmp::min(n, n_remaining));
 
-        let lazer = self.difficulty.get_lazer();
-        let using_classic_slider_acc = self.difficulty.get_mods().no_slider_head_acc(lazer);
+        let (origin, _) = self.score_origin(&attrs);
+
+        let (slider_end_hits, large_tick_hits, small_tick_hits) = match origin {
+            OsuScoreOrigin::Stable => (0, 0, 0),
+            OsuScoreOrigin::WithSliderAcc {
+                max_large_ticks,
+                max_slider_ends,
+            } => {
+                let slider_end_hits = self
+                    .slider_end_hits
+                    .map_or(max_slider_ends, |n| cmp::min(n, max_slider_ends));
 
-        let (origin, slider_end_hits, large_tick_hits, small_tick_hits) =
-            match (lazer, using_classic_slider_acc) {
-                (false, _) => (OsuScoreOrigin::Stable, 0, 0, 0),
-                (true, false) => {
-                    let origin = OsuScoreOrigin::WithSliderAcc {
-                        max_large_ticks: attrs.n_large_ticks,
-                        max_slider_ends: attrs.n_sliders,
-                    };
+                let large_tick_hits = self
+                    .large_tick_hits
+                    .map_or(max_large_ticks, |n| cmp::min(n, max_large_ticks));
 
-                    let slider_end_hits = self
-                        .slider_end_hits
-                        .map_or(attrs.n_sliders, |n| cmp::min(n, attrs.n_sliders));
+                (slider_end_hits, large_tick_hits, 0)
+            }
+            OsuScoreOrigin::WithoutSliderAcc {
+                max_large_ticks,
+                max_small_ticks,
+            } => {
+                let small_tick_hits = self
+                    .small_tick_hits
+                    .map_or(max_small_ticks, |n| cmp::min(n, max_small_ticks));
 
-                    let large_tick_hits = self
-                        .large_tick_hits
-                        .map_or(attrs.n_large_ticks, |n| cmp::min(n, attrs.n_large_ticks));
+                let large_tick_hits = self
+                    .large_tick_hits
+                    .map_or(max_large_ticks, |n| cmp::min(n, max_large_ticks));
 
-                    (origin, slider_end_hits, large_tick_hits, 0)
-                }
-                (true, true) => {
-                    let origin = OsuScoreOrigin::WithoutSliderAcc {
-                        max_large_ticks: attrs.n_sliders + attrs.n_large_ticks,
-                        max_small_ticks: attrs.n_sliders,
-                    };
-
-                    let small_tick_hits = self
-                        .small_tick_hits
-                        .map_or(attrs.n_sliders, |n| cmp::min(n, attrs.n_sliders));
-
-                    let large_tick_hits = self
-                        .large_tick_hits
-                        .map_or(attrs.n_sliders + attrs.n_large_ticks, |n| {
-                            cmp::min(n, attrs.n_sliders + attrs.n_large_ticks)
-                        });
-
-                    (origin, 0, large_tick_hits, small_tick_hits)
-                }
-            };
+                (0, large_tick_hits, small_tick_hits)
+            }
+        };
 
         let (slider_acc_value, max_slider_acc_value) = match origin {
             OsuScoreOrigin::Stable => (0, 0),
@@ -717,51 +786,357 @@ impl<'map> OsuPerformance<'map> {
     }
 
     /// Calculate all performance related values, including pp and stars.
-    pub fn calculate(mut self) -> Result<OsuPerformanceAttributes, ConvertError> {
+    ///
+    /// Returns [`ConvertError::MissingAttributes`] if the calculator was
+    /// created from [`OsuVanillaDifficultyAttributes`] or
+    /// [`OsuVanillaPerformanceAttributes`] because those don't carry the
+    /// values of the PP+ model.
+    ///
+    /// [`OsuVanillaDifficultyAttributes`]: crate::osu::OsuVanillaDifficultyAttributes
+    pub fn calculate(self) -> Result<OsuPerformanceAttributes, ConvertError> {
+        self.explain().map(|breakdown| breakdown.attributes)
+    }
+
+    /// Calculate all performance related values and list every factor that
+    /// went into them.
+    ///
+    /// The final pp can be reconstructed from the factors of the returned
+    /// [`OsuPerformanceBreakdown`].
+    ///
+    /// Same as [`OsuPerformance::calculate`], this requires a map or PP+
+    /// attributes.
+    pub fn explain(mut self) -> Result<OsuPerformanceBreakdown, ConvertError> {
+        let attrs = self.pplus_attributes()?;
         let state = self.generate_state()?;
 
-        let attrs = match self.map_or_attrs {
-            MapOrAttrs::Attrs(attrs) => attrs,
-            MapOrAttrs::Map(ref map) => self.difficulty.calculate_for_mode::<Osu>(map)?,
+        Ok(self.setup(attrs).calculate_breakdown(state))
+    }
+
+    /// Calculate all performance related values with the
+    /// [`ScoringModel::Vanilla`] model.
+    ///
+    /// Returns [`ConvertError::MissingAttributes`] if the calculator was
+    /// created from [`OsuDifficultyAttributes`] or
+    /// [`OsuPerformanceAttributes`] because those don't carry the values
+    /// of the vanilla model.
+    ///
+    /// [`ScoringModel::Vanilla`]: crate::osu::ScoringModel::Vanilla
+    pub fn calculate_vanilla(mut self) -> Result<OsuVanillaPerformanceAttributes, ConvertError> {
+        let attrs = self.cache_vanilla_attributes()?;
+        let state = self.generate_state()?;
+
+        let shared = attrs.shared();
+        let (origin, using_classic_slider_acc) = self.score_origin(&shared);
+        let effective_miss_count = calculate_effective_miss_count(&shared, &state, &origin);
+        let acc = state.accuracy(origin);
+
+        let inner = OsuVanillaPerformanceCalculator::new(
+            attrs,
+            self.difficulty.get_mods(),
+            acc,
+            state,
+            effective_miss_count,
+            using_classic_slider_acc,
+        );
+
+        Ok(inner.calculate())
+    }
+
+    /// The [`ScoringModel`] that [`Performance`] calculates with.
+    ///
+    /// That's the specified model unless the calculator was created from
+    /// attributes of the other model.
+    ///
+    /// [`Performance`]: crate::Performance
+    pub(crate) fn available_scoring_model(&self) -> ScoringModel {
+        match (&self.map_or_attrs, &self.vanilla_attrs) {
+            (MapOrAttrs::Map(_), _) => self.difficulty.get_scoring_model(),
+            (MapOrAttrs::Attrs(_), Some(_)) => ScoringModel::Vanilla,
+            (MapOrAttrs::Attrs(_), None) => ScoringModel::PpPlus,
+        }
+    }
+
+    /// Calculate the difficulty attributes and, if `miss_indices` are
+    /// given, weight them by the strains of each skill.
+    fn calculate_attributes(
+        difficulty: &Difficulty,
+        map: &Beatmap,
+        miss_indices: Option<&[usize]>,
+    ) -> Result<(OsuDifficultyAttributes, Option<PositionalMisses>), ConvertError> {
+        let Some(miss_indices) = miss_indices else {
+            return difficulty
+                .calculate_for_mode::<Osu>(map)
+                .map(|attrs| (attrs, None));
         };
 
-        let mods = self.difficulty.get_mods();
-        let lazer = self.difficulty.get_lazer();
-        let using_classic_slider_acc = mods.no_slider_head_acc(lazer);
+        let mods = difficulty.get_mods();
+        let map = map.convert_ref(GameMode::Osu, mods)?;
 
-        let mut effective_miss_count = f64::from(state.misses);
+        let DifficultyValues { skills, mut attrs } = DifficultyValues::calculate(difficulty, &map);
 
-        if attrs.n_sliders > 0 {
-            if using_classic_slider_acc {
-                // * Consider that full combo is maximum combo minus dropped slider tails since they don't contribute to combo but also don't break it
-                // * In classic scores we can't know the amount of dropped sliders so we estimate to 10% of all sliders on the map
-                let full_combo_threshold =
-                    f64::from(attrs.max_combo) - 0.1 * f64::from(attrs.n_sliders);
+        DifficultyValues::eval(&mut attrs, mods, &skills);
+        let positional_misses = PositionalMisses::new(&skills, miss_indices);
 
-                if f64::from(state.max_combo) < full_combo_threshold {
-                    effective_miss_count =
-                        full_combo_threshold / f64::from(state.max_combo).max(1.0);
-                }
+        Ok((attrs, Some(positional_misses)))
+    }
 
-                // * In classic scores there can't be more misses than a sum of all non-perfect judgements
-                effective_miss_count = effective_miss_count.min(total_imperfect_hits(&state));
-            } else {
-                let full_combo_threshold =
-                    f64::from(attrs.max_combo - n_slider_ends_dropped(&attrs, &state));
+    /// Find the minimal accuracy between `0.0` and `100.0` that is required
+    /// to reach `target_pp`.
+    ///
+    /// All other parameters such as mods, combo, or misses are kept fixed
+    /// while 300s, 100s, and 50s are generated from the accuracy.
+    /// Difficulty attributes are only calculated once.
+    ///
+    /// Returns `None` if `target_pp` cannot be reached even with 100%
+    /// accuracy.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn required_accuracy(mut self, target_pp: f64) -> Result<Option<f64>, ConvertError> {
+        let attrs = self.prepare_accuracy()?;
+        let setup = self.setup(attrs);
+
+        Ok(solver::min_accuracy(target_pp, |acc| {
+            let state = self.clone().accuracy(acc).generate_state();
+
+            setup
+                .calculate_breakdown(state.expect("no conversion required"))
+                .attributes
+                .pp
+        }))
+    }
 
-                if f64::from(state.max_combo) < full_combo_threshold {
-                    effective_miss_count =
-                        full_combo_threshold / f64::from(state.max_combo).max(1.0);
-                }
+    /// Find the maximal amount of misses that still reaches `target_pp`.
+    ///
+    /// All other parameters such as mods, combo, or accuracy are kept fixed.
+    /// Difficulty attributes are only calculated once.
+    ///
+    /// Returns `None` if `target_pp` cannot be reached even without misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn allowed_misses(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let attrs = self.pplus_attributes()?;
+        let setup = self.setup(attrs);
+        let max_misses = self.clone().generate_state()?.total_hits();
+
+        Ok(solver::max_misses(target_pp, max_misses, |misses| {
+            let state = self.clone().misses(misses).generate_state();
+
+            setup
+                .calculate_breakdown(state.expect("no conversion required"))
+                .attributes
+                .pp
+        }))
+    }
 
-                // * Combine regular misses with tick misses since tick misses break combo as well
-                effective_miss_count = effective_miss_count
-                    .min(f64::from(n_large_tick_miss(&attrs, &state) + state.misses));
-            }
+    /// Calculate the performance attributes for each of the given accuracies
+    /// between `0.0` and `100.0`.
+    ///
+    /// All other parameters such as mods, combo, or misses are kept fixed
+    /// while 300s, 100s, and 50s are generated from the accuracy.
+    /// Difficulty attributes, as well as all factors that only depend on the
+    /// map and mods, are only calculated once and shared across all
+    /// accuracies.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn accuracy_curve(
+        mut self,
+        accuracies: &[f64],
+    ) -> Result<Vec<OsuPerformanceAttributes>, ConvertError> {
+        let attrs = self.prepare_accuracy()?;
+        let setup = self.setup(attrs);
+
+        let curve = accuracies
+            .iter()
+            .map(|&acc| {
+                let state = self.clone().accuracy(acc).generate_state();
+
+                setup
+                    .calculate_breakdown(state.expect("no conversion required"))
+                    .attributes
+            })
+            .collect();
+
+        Ok(curve)
+    }
+
+    /// Calculate the performance attributes for each of the given amounts of
+    /// misses.
+    ///
+    /// All other parameters such as mods, combo, or accuracy are kept fixed.
+    /// Difficulty attributes, as well as all factors that only depend on the
+    /// map and mods, are only calculated once and shared across all amounts
+    /// of misses.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn miss_curve(
+        mut self,
+        misses: &[u32],
+    ) -> Result<Vec<OsuPerformanceAttributes>, ConvertError> {
+        let attrs = self.pplus_attributes()?;
+        let setup = self.setup(attrs);
+
+        let curve = misses
+            .iter()
+            .map(|&n_misses| {
+                let state = self.clone().misses(n_misses).generate_state();
+
+                setup
+                    .calculate_breakdown(state.expect("no conversion required"))
+                    .attributes
+            })
+            .collect();
+
+        Ok(curve)
+    }
+
+    /// Same as [`OsuPerformance::required_accuracy`] but with the
+    /// [`ScoringModel::Vanilla`] model.
+    pub(crate) fn required_accuracy_vanilla(
+        mut self,
+        target_pp: f64,
+    ) -> Result<Option<f64>, ConvertError> {
+        self.cache_vanilla_attributes()?;
+        self.reset_accuracy_hitresults();
+
+        Ok(solver::min_accuracy(target_pp, |acc| {
+            let calc = self.clone().accuracy(acc);
+
+            calc.calculate_vanilla().expect("no conversion required").pp
+        }))
+    }
+
+    /// Same as [`OsuPerformance::allowed_misses`] but with the
+    /// [`ScoringModel::Vanilla`] model.
+    pub(crate) fn allowed_misses_vanilla(
+        mut self,
+        target_pp: f64,
+    ) -> Result<Option<u32>, ConvertError> {
+        self.cache_vanilla_attributes()?;
+        let max_misses = self.clone().generate_state()?.total_hits();
+
+        Ok(solver::max_misses(target_pp, max_misses, |misses| {
+            let calc = self.clone().misses(misses);
+
+            calc.calculate_vanilla().expect("no conversion required").pp
+        }))
+    }
+
+    /// Same as [`OsuPerformance::accuracy_curve`] but with the
+    /// [`ScoringModel::Vanilla`] model.
+    pub(crate) fn accuracy_curve_vanilla(
+        mut self,
+        accuracies: &[f64],
+    ) -> Result<Vec<OsuVanillaPerformanceAttributes>, ConvertError> {
+        self.cache_vanilla_attributes()?;
+        self.reset_accuracy_hitresults();
+
+        accuracies
+            .iter()
+            .map(|&acc| self.clone().accuracy(acc).calculate_vanilla())
+            .collect()
+    }
+
+    /// Same as [`OsuPerformance::miss_curve`] but with the
+    /// [`ScoringModel::Vanilla`] model.
+    pub(crate) fn miss_curve_vanilla(
+        mut self,
+        misses: &[u32],
+    ) -> Result<Vec<OsuVanillaPerformanceAttributes>, ConvertError> {
+        self.cache_vanilla_attributes()?;
+
+        misses
+            .iter()
+            .map(|&n_misses| self.clone().misses(n_misses).calculate_vanilla())
+            .collect()
+    }
+
+    /// Cache the difficulty attributes and reset all hitresults that are
+    /// generated from the accuracy.
+    fn prepare_accuracy(&mut self) -> Result<OsuDifficultyAttributes, ConvertError> {
+        let attrs = self.pplus_attributes()?;
+        self.reset_accuracy_hitresults();
+
+        Ok(attrs)
+    }
+
+    /// Reset all hitresults that are generated from the accuracy.
+    const fn reset_accuracy_hitresults(&mut self) {
+        self.n300 = None;
+        self.n100 = None;
+        self.n50 = None;
+    }
+
+    /// Same as [`OsuPerformance::cache_attributes`] but fails if only
+    /// vanilla attributes are available.
+    fn pplus_attributes(&mut self) -> Result<OsuDifficultyAttributes, ConvertError> {
+        // Vanilla attributes only fill the values that both models share
+        if self.vanilla_attrs.is_some() {
+            return Err(ConvertError::MissingAttributes {
+                model: ScoringModel::PpPlus,
+            });
         }
 
-        effective_miss_count = effective_miss_count.max(f64::from(state.misses));
-        effective_miss_count = effective_miss_count.min(f64::from(state.total_hits()));
+        self.cache_attributes()
+    }
+
+    /// Calculate the vanilla difficulty attributes so that repeated
+    /// calculations on clones don't need to.
+    fn cache_vanilla_attributes(&mut self) -> Result<OsuVanillaDifficultyAttributes, ConvertError> {
+        let attrs = match (&self.vanilla_attrs, &self.map_or_attrs) {
+            (Some(attrs), _) => attrs,
+            (None, MapOrAttrs::Map(map)) => {
+                let attrs = super::vanilla_difficulty(&self.difficulty, map)?;
+                self.map_or_attrs.insert_attrs(attrs.shared());
+
+                self.vanilla_attrs.insert(Box::new(attrs))
+            }
+            (None, MapOrAttrs::Attrs(_)) => {
+                return Err(ConvertError::MissingAttributes {
+                    model: ScoringModel::Vanilla,
+                })
+            }
+        };
+
+        Ok((**attrs).clone())
+    }
+
+    /// Calculate the difficulty attributes and positional misses so that
+    /// repeated calculations on clones don't need to.
+    fn cache_attributes(&mut self) -> Result<OsuDifficultyAttributes, ConvertError> {
+        let attrs = match self.map_or_attrs {
+            MapOrAttrs::Map(ref map) => {
+                let (attrs, positional_misses) = Self::calculate_attributes(
+                    &self.difficulty,
+                    map,
+                    self.miss_indices.as_deref(),
+                )?;
+                self.positional_misses = positional_misses;
+
+                self.map_or_attrs.insert_attrs(attrs)
+            }
+            MapOrAttrs::Attrs(ref attrs) => attrs,
+        };
+
+        Ok(attrs.clone())
+    }
+
+    /// Build everything that does not depend on the score state so that it
+    /// can be shared across multiple states.
+    fn setup(&self, attrs: OsuDifficultyAttributes) -> OsuPerformanceSetup<'_> {
+        let (origin, using_classic_slider_acc) = self.score_origin(&attrs);
+
+        OsuPerformanceSetup::new(
+            attrs,
+            self.difficulty.get_mods(),
+            origin,
+            using_classic_slider_acc,
+            self.hit_error,
+            self.positional_misses,
+            self.difficulty.get_pplus_config(),
+        )
+    }
+
+    /// The origin of a score on a map with the given attributes and whether
+    /// slider heads are judged like circles.
+    fn score_origin(&self, attrs: &OsuDifficultyAttributes) -> (OsuScoreOrigin, bool) {
+        let lazer = self.difficulty.get_lazer();
+        let using_classic_slider_acc = self.difficulty.get_mods().no_slider_head_acc(lazer);
 
         let origin = match (lazer, using_classic_slider_acc) {
             (false, _) => OsuScoreOrigin::Stable,
@@ -775,18 +1150,7 @@ impl<'map> OsuPerformance<'map> {
             },
         };
 
-        let acc = state.accuracy(origin);
-
-        let inner = OsuPerformanceCalculator::new(
-            attrs,
-            mods,
-            acc,
-            state,
-            effective_miss_count,
-            using_classic_slider_acc,
-        );
-
-        Ok(inner.calculate())
+        (origin, using_classic_slider_acc)
     }
 
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Osu>) -> Self {
@@ -802,6 +1166,10 @@ impl<'map> OsuPerformance<'map> {
             n100: None,
             n50: None,
             misses: None,
+            hit_error: None,
+            miss_indices: None,
+            positional_misses: None,
+            vanilla_attrs: None,
             hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
@@ -838,6 +1206,72 @@ impl<'map, T: IntoModePerformance<'map, Osu>> From<T> for OsuPerformance<'map> {
     }
 }
 
+/// Misses including an estimated amount of slider breaks.
+pub(super) fn calculate_effective_miss_count(
+    attrs: &OsuDifficultyAttributes,
+    state: &OsuScoreState,
+    origin: &OsuScoreOrigin,
+) -> f64 {
+    let mut effective_miss_count = f64::from(state.misses);
+
+    if attrs.n_sliders > 0 {
+        match origin {
+            OsuScoreOrigin::Stable => {
+                // * Consider that full combo is maximum combo minus dropped slider tails since they don't contribute to combo but also don't break it
+                // * In classic scores we can't know the amount of dropped sliders so we estimate to 10% of all sliders on the map
+                let full_combo_threshold =
+                    f64::from(attrs.max_combo) - 0.1 * f64::from(attrs.n_sliders);
+
+                if f64::from(state.max_combo) < full_combo_threshold {
+                    effective_miss_count =
+                        full_combo_threshold / f64::from(state.max_combo).max(1.0);
+                }
+
+                // * In classic scores there can't be more misses than a sum of all non-perfect judgements
+                effective_miss_count = effective_miss_count.min(total_imperfect_hits(state));
+            }
+            OsuScoreOrigin::WithoutSliderAcc {
+                max_large_ticks, ..
+            } => {
+                // * Slider ends don't break combo so the amount of dropped
+                // * ones is still estimated, just like for stable scores
+                let full_combo_threshold =
+                    f64::from(attrs.max_combo) - 0.1 * f64::from(attrs.n_sliders);
+
+                if f64::from(state.max_combo) < full_combo_threshold {
+                    effective_miss_count =
+                        full_combo_threshold / f64::from(state.max_combo).max(1.0);
+                }
+
+                // * Missed slider heads, ticks, and repeats are known and
+                // * break combo as well
+                let large_tick_misses = max_large_ticks.saturating_sub(state.large_tick_hits);
+
+                effective_miss_count = effective_miss_count
+                    .min(total_imperfect_hits(state))
+                    .min(f64::from(large_tick_misses + state.misses));
+            }
+            OsuScoreOrigin::WithSliderAcc { .. } => {
+                let full_combo_threshold =
+                    f64::from(attrs.max_combo - n_slider_ends_dropped(attrs, state));
+
+                if f64::from(state.max_combo) < full_combo_threshold {
+                    effective_miss_count =
+                        full_combo_threshold / f64::from(state.max_combo).max(1.0);
+                }
+
+                // * Combine regular misses with tick misses since tick misses break combo as well
+                effective_miss_count = effective_miss_count
+                    .min(f64::from(n_large_tick_miss(attrs, state) + state.misses));
+            }
+        }
+    }
+
+    effective_miss_count = effective_miss_count.max(f64::from(state.misses));
+
+    effective_miss_count.min(f64::from(state.total_hits()))
+}
+
 fn total_imperfect_hits(state: &OsuScoreState) -> f64 {
     f64::from(state.n100 + state.n50 + state.misses)
 }
@@ -1315,4 +1749,366 @@ mod test {
         assert!(OsuPerformance::try_new(&map).is_none());
         assert!(OsuPerformance::try_new(map).is_none());
     }
+
+    #[test]
+    fn pplus_config() {
+        let calc = || OsuPerformance::from(attrs()).combo(500).misses(2);
+
+        let regular = calc().calculate().unwrap();
+        let default = calc()
+            .pplus_config(PpPlusConfig::default())
+            .calculate()
+            .unwrap();
+
+        assert_eq!(regular, default);
+
+        let no_length_bonus = calc()
+            .pplus_config(PpPlusConfig {
+                length_bonus: false,
+                ..PpPlusConfig::default()
+            })
+            .calculate()
+            .unwrap();
+
+        assert!(no_length_bonus.pp_aim < regular.pp_aim);
+        assert!((no_length_bonus.pp_stamina - regular.pp_stamina).abs() < f64::EPSILON);
+
+        let classic_combo = calc()
+            .pplus_config(PpPlusConfig {
+                combo_scaling_rework: false,
+                ..PpPlusConfig::default()
+            })
+            .calculate()
+            .unwrap();
+
+        assert!(classic_combo.pp != regular.pp);
+    }
+
+    #[test]
+    fn effective_miss_count() {
+        let calc = |lazer: bool| {
+            OsuPerformance::from(attrs())
+                .lazer(lazer)
+                .combo(300)
+                .n100(5)
+                .misses(1)
+        };
+
+        let stable = calc(false).calculate().unwrap();
+        let lazer = calc(true)
+            .large_tick_hits(attrs().n_large_ticks - 5)
+            .calculate()
+            .unwrap();
+
+        assert!(stable.effective_miss_count > 1.0);
+        assert!(lazer.effective_miss_count > 1.0);
+
+        // All large ticks hit so only the actual miss can break combo
+        let lazer_ticks = calc(true)
+            .large_tick_hits(attrs().n_large_ticks)
+            .calculate()
+            .unwrap();
+
+        assert!((lazer_ticks.effective_miss_count - 1.0).abs() < f64::EPSILON);
+
+        let no_estimate = calc(false)
+            .pplus_config(PpPlusConfig {
+                effective_miss_count: false,
+                ..PpPlusConfig::default()
+            })
+            .calculate()
+            .unwrap();
+
+        assert!((no_estimate.effective_miss_count - 1.0).abs() < f64::EPSILON);
+        assert!(no_estimate.pp > stable.pp);
+    }
+
+    #[test]
+    fn unstable_rate() {
+        let calc = || OsuPerformance::from(attrs()).n100(20).misses(1);
+
+        let estimated = calc().calculate().unwrap();
+        let estimated_ur = estimated.estimated_unstable_rate.unwrap();
+
+        let known = calc().unstable_rate(estimated_ur).calculate().unwrap();
+        assert!((known.pp - estimated.pp).abs() < 1e-9);
+
+        let better = calc().unstable_rate(estimated_ur / 2.0).calculate().unwrap();
+        assert!(better.pp_acc > estimated.pp_acc);
+        assert!(better.pp_aim > estimated.pp_aim);
+        assert_eq!(better.estimated_unstable_rate, Some(estimated_ur / 2.0));
+
+        let hit_error = calc().hit_error(estimated_ur / 20.0).calculate().unwrap();
+        assert_eq!(hit_error, better);
+
+        for invalid in [0.0, -50.0, f64::NAN, f64::INFINITY] {
+            let ignored = calc().unstable_rate(invalid).calculate().unwrap();
+            assert_eq!(ignored, estimated);
+
+            let ignored = calc().hit_error(invalid).calculate().unwrap();
+            assert_eq!(ignored, estimated);
+        }
+
+        let no_300s = OsuPerformance::from(attrs())
+            .n300(0)
+            .n100(0)
+            .n50(0)
+            .misses(attrs().n_objects())
+            .calculate()
+            .unwrap();
+        assert_eq!(no_300s.estimated_unstable_rate, None);
+    }
+
+    #[test]
+    fn flashlight() {
+        let calc = |mods: u32| {
+            OsuPerformance::from(attrs())
+                .mods(mods)
+                .combo(500)
+                .misses(2)
+                .calculate()
+                .unwrap()
+        };
+
+        let nomod = calc(0);
+        let fl = calc(1024);
+
+        assert!(attrs().flashlight > 0.0);
+        assert!(nomod.pp_flashlight.abs() < f64::EPSILON);
+        assert!(fl.pp_flashlight > 0.0);
+        assert!(fl.pp > nomod.pp);
+    }
+
+    #[test]
+    fn explain() {
+        fn reconstruct(breakdown: &OsuPerformanceBreakdown, fl: bool) -> f64 {
+            let aim_weight = breakdown.aim_accuracy_weight * breakdown.aim_combo_weight;
+            let speed_weight = breakdown.speed_accuracy_weight * breakdown.speed_combo_weight;
+
+            let aim = aim_weight
+                * breakdown.aim.base_value
+                * breakdown.aim.miss_weight
+                * breakdown.slider_nerf_factor
+                * breakdown.length_bonus;
+            let reading = aim_weight
+                * breakdown.reading.base_value
+                * breakdown.reading.miss_weight
+                * breakdown.length_bonus;
+            let speed = speed_weight
+                * breakdown.speed.base_value
+                * breakdown.speed.miss_weight
+                * breakdown.length_bonus;
+            let stamina =
+                speed_weight * breakdown.stamina.base_value * breakdown.stamina.miss_weight;
+            let flashlight = if fl {
+                aim_weight
+                    * breakdown.flashlight_length_weight
+                    * breakdown.flashlight.base_value
+                    * breakdown.flashlight.miss_weight
+            } else {
+                0.0
+            };
+            let acc = breakdown.accuracy_base_value
+                * breakdown.accuracy_length_weight
+                * breakdown.accuracy_mod_weight;
+
+            (aim.powf(1.1)
+                + reading.powf(1.1)
+                + speed.max(stamina).powf(1.1)
+                + flashlight.powf(1.1)
+                + acc.powf(1.1))
+            .powf(1.0 / 1.1)
+                * breakdown.base_multiplier
+                * breakdown.no_fail_multiplier
+                * breakdown.spun_out_multiplier
+        }
+
+        // NM, NF, HDFL, SO, RX
+        for mods in [0, 1, 1032, 4096, 128] {
+            for combo_scaling_rework in [true, false] {
+                let calc = || {
+                    OsuPerformance::from(attrs())
+                        .mods(mods)
+                        .pplus_config(PpPlusConfig {
+                            combo_scaling_rework,
+                            ..PpPlusConfig::default()
+                        })
+                        .combo(500)
+                        .slider_end_hits(attrs().n_sliders - 10)
+                        .n100(10)
+                        .n50(3)
+                        .misses(2)
+                };
+
+                let breakdown = calc().explain().unwrap();
+                let attrs = &breakdown.attributes;
+
+                assert_eq!(attrs, &calc().calculate().unwrap());
+                assert!((reconstruct(&breakdown, mods & 1024 > 0) - attrs.pp).abs() < 1e-9);
+                assert!(
+                    (breakdown.effective_miss_count + breakdown.relax_miss_adjustment
+                        - attrs.effective_miss_count)
+                        .abs()
+                        < 1e-9
+                );
+                assert_eq!(breakdown.relax_miss_adjustment > 0.0, mods == 128);
+            }
+        }
+
+        // Reading is a separate portion below AR8
+        let low_ar = Difficulty::new()
+            .ar(5.0, false)
+            .calculate_for_mode::<Osu>(&beatmap())
+            .unwrap();
+        let breakdown = OsuPerformance::from(low_ar).misses(2).explain().unwrap();
+
+        assert!(breakdown.attributes.pp_reading > 0.0);
+        assert!((reconstruct(&breakdown, false) - breakdown.attributes.pp).abs() < 1e-9);
+    }
+
+    #[test]
+    fn miss_indices() {
+        let map = beatmap();
+        let infos = Difficulty::new().osu_objects(&map).unwrap();
+
+        let hardest = infos
+            .iter()
+            .max_by(|a, b| a.jump_aim.total_cmp(&b.jump_aim))
+            .unwrap()
+            .idx;
+        let easiest = infos
+            .iter()
+            .min_by(|a, b| a.jump_aim.total_cmp(&b.jump_aim))
+            .unwrap()
+            .idx;
+
+        let calc = || OsuPerformance::from(&map).misses(1);
+
+        let unknown = calc().calculate().unwrap();
+        let hard = calc().miss_indices(&[hardest]).calculate().unwrap();
+        let easy = calc().miss_indices(&[easiest]).calculate().unwrap();
+
+        assert!(easy.pp_jump_aim > hard.pp_jump_aim);
+        assert!(easy.pp > hard.pp);
+        assert!((hard.pp_jump_aim - unknown.pp_jump_aim).abs() < 1e-9);
+
+        // Caching the attributes beforehand keeps the miss positions
+        let mut generated = calc().miss_indices(&[easiest]);
+        generated.generate_state().unwrap();
+        assert_eq!(generated.calculate().unwrap(), easy);
+
+        // Attributes don't contain the strain of each object
+        let from_attrs = OsuPerformance::from(attrs())
+            .misses(1)
+            .miss_indices(&[easiest])
+            .calculate()
+            .unwrap();
+
+        assert_eq!(from_attrs, unknown);
+    }
+
+    #[test]
+    fn solvers_keep_miss_indices() {
+        let map = beatmap();
+        let mut infos = Difficulty::new().osu_objects(&map).unwrap();
+        infos.sort_by(|a, b| a.jump_aim.total_cmp(&b.jump_aim));
+        let easiest: Vec<_> = infos.iter().take(3).map(|info| info.idx).collect();
+
+        let calc = || OsuPerformance::from(&map).misses(3).miss_indices(&easiest);
+
+        let unknown = OsuPerformance::from(&map).misses(3).calculate().unwrap();
+        let target_pp = unknown.pp;
+        assert!(calc().calculate().unwrap().pp > target_pp);
+
+        let acc = calc().required_accuracy(target_pp).unwrap().unwrap();
+        assert!(acc < 100.0);
+        assert!(calc().accuracy(acc).calculate().unwrap().pp >= target_pp);
+        assert!(calc().accuracy(acc - 1.0).calculate().unwrap().pp < target_pp);
+
+        let misses = calc().allowed_misses(target_pp).unwrap().unwrap();
+        assert!(misses >= 3);
+        assert!(calc().misses(misses).calculate().unwrap().pp >= target_pp);
+        assert!(calc().misses(misses + 1).calculate().unwrap().pp < target_pp);
+    }
+
+    #[test]
+    fn curves_keep_miss_indices() {
+        let map = beatmap();
+        let infos = Difficulty::new().osu_objects(&map).unwrap();
+        let indices: Vec<_> = infos.iter().take(5).map(|info| info.idx).collect();
+
+        let calc = || {
+            OsuPerformance::from(&map)
+                .lazer(true)
+                .combo(500)
+                .misses(2)
+                .miss_indices(&indices[..2])
+        };
+
+        let accuracies = [90.0, 95.0, 99.0];
+        let acc_curve = calc().accuracy_curve(&accuracies).unwrap();
+
+        for (&acc, attrs) in accuracies.iter().zip(acc_curve) {
+            assert_eq!(attrs, calc().accuracy(acc).calculate().unwrap());
+        }
+
+        let misses = [0, 2, 5];
+        let miss_curve = calc().miss_indices(&indices).miss_curve(&misses).unwrap();
+
+        for (&n_misses, attrs) in misses.iter().zip(miss_curve) {
+            let expected = calc()
+                .miss_indices(&indices)
+                .misses(n_misses)
+                .calculate()
+                .unwrap();
+
+            assert_eq!(attrs, expected);
+        }
+
+        let unknown = OsuPerformance::from(&map)
+            .lazer(true)
+            .combo(500)
+            .misses(2)
+            .calculate()
+            .unwrap();
+
+        assert_ne!(calc().miss_curve(&[2]).unwrap()[0], unknown);
+    }
+
+    #[test]
+    fn slider_nerf() {
+        let calc = || {
+            OsuPerformance::from(attrs())
+                .lazer(true)
+                .combo(500)
+                .n100(10)
+        };
+
+        let followed = calc().explain().unwrap();
+        let dropped = calc()
+            .slider_end_hits(attrs().n_sliders - 30)
+            .explain()
+            .unwrap();
+
+        assert!((followed.slider_nerf_factor - 1.0).abs() < f64::EPSILON);
+        assert!(dropped.slider_nerf_factor < 1.0);
+        assert!(dropped.slider_nerf_factor >= calculator::SLIDER_NERF_MIN_FACTOR);
+
+        let (followed, dropped) = (&followed.attributes, &dropped.attributes);
+        assert!(dropped.pp_aim < followed.pp_aim);
+        assert!(dropped.pp_precision < followed.pp_precision);
+        assert!(dropped.pp_aim >= followed.pp_aim * calculator::SLIDER_NERF_MIN_FACTOR);
+
+        let classic_followed = calc().lazer(false).calculate().unwrap();
+        let classic_dropped = calc()
+            .lazer(false)
+            .slider_end_hits(attrs().n_sliders - 30)
+            .explain()
+            .unwrap();
+
+        assert!((classic_dropped.slider_nerf_factor - 1.0).abs() < f64::EPSILON);
+        assert!(
+            (classic_followed.pp_aim - classic_dropped.attributes.pp_aim).abs() < f64::EPSILON
+        );
+    }
 }
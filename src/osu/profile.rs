@@ -0,0 +1,159 @@
+//! The totals are weighted the same way as a player's total pp on osu!:
+//! values are sorted in descending order and the `n`-th value is weighted by
+//! `0.95^n`. Each skill is sorted on its own so a player's best jump aim
+//! score does not need to be their best precision score.
+//!
+//! Scores are expected to be the player's best score on each map.
+//!
+//! # Example
+//!
+//! ```
+//! use rosu_pp::{Beatmap, Difficulty};
+//! use rosu_pp::osu::{Osu, OsuPerformance};
+//! use rosu_pp::osu::profile::OsuProfile;
+//!
+//! let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+//! let attrs = Difficulty::new().calculate_for_mode::<Osu>(&map).unwrap();
+//!
+//! let scores: Vec<_> = [100.0, 98.5, 96.0]
+//!     .into_iter()
+//!     .map(|acc| {
+//!         OsuPerformance::from(attrs.clone())
+//!             .accuracy(acc)
+//!             .calculate()
+//!             .unwrap()
+//!     })
+//!     .collect();
+//!
+//! let profile = OsuProfile::new(&scores);
+//!
+//! println!("Total: {:.2}pp", profile.pp);
+//! println!("Jump aim: {:.2}pp", profile.pp_jump_aim);
+//! ```
+
+use super::OsuPerformanceAttributes;
+
+/// Weighted per-skill totals of a player's PP+ scores.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OsuProfile {
+    /// The weighted total pp including [`OsuProfile::bonus_pp`].
+    pub pp: f64,
+    /// Bonus pp for the amount of scores.
+    pub bonus_pp: f64,
+    /// The weighted total aim pp.
+    pub pp_aim: f64,
+    /// The weighted total jump aim pp.
+    pub pp_jump_aim: f64,
+    /// The weighted total flow aim pp.
+    pub pp_flow_aim: f64,
+    /// The weighted total precision pp.
+    pub pp_precision: f64,
+    /// The weighted total reading pp.
+    pub pp_reading: f64,
+    /// The weighted total speed pp.
+    pub pp_speed: f64,
+    /// The weighted total stamina pp.
+    pub pp_stamina: f64,
+    /// The weighted total flashlight pp.
+    pub pp_flashlight: f64,
+    /// The weighted total accuracy pp.
+    pub pp_acc: f64,
+    /// The amount of scores that were aggregated.
+    pub n_scores: usize,
+}
+
+impl OsuProfile {
+    /// Factor by which each score is weighted less than the previous one.
+    pub const WEIGHT_DECAY: f64 = 0.95;
+    /// Maximum amount of scores that count towards [`OsuProfile::bonus_pp`].
+    pub const BONUS_MAX_SCORES: usize = 1000;
+
+    /// Aggregate the given scores.
+    pub fn new<'a>(scores: impl IntoIterator<Item = &'a OsuPerformanceAttributes>) -> Self {
+        let scores: Vec<_> = scores.into_iter().collect();
+        let total = |pp: fn(&OsuPerformanceAttributes) -> f64| {
+            Self::weighted_total(scores.iter().map(|score| pp(score)))
+        };
+
+        let bonus_pp = Self::bonus_pp(scores.len());
+
+        Self {
+            pp: total(|score| score.pp) + bonus_pp,
+            bonus_pp,
+            pp_aim: total(|score| score.pp_aim),
+            pp_jump_aim: total(|score| score.pp_jump_aim),
+            pp_flow_aim: total(|score| score.pp_flow_aim),
+            pp_precision: total(|score| score.pp_precision),
+            pp_reading: total(|score| score.pp_reading),
+            pp_speed: total(|score| score.pp_speed),
+            pp_stamina: total(|score| score.pp_stamina),
+            pp_flashlight: total(|score| score.pp_flashlight),
+            pp_acc: total(|score| score.pp_acc),
+            n_scores: scores.len(),
+        }
+    }
+
+    /// Sort the values in descending order and sum them up while weighting
+    /// the `n`-th value by `0.95^n`.
+    pub fn weighted_total(values: impl IntoIterator<Item = f64>) -> f64 {
+        let mut values: Vec<_> = values.into_iter().collect();
+        values.sort_unstable_by(|a, b| b.total_cmp(a));
+
+        values
+            .into_iter()
+            .zip(0..)
+            .map(|(value, i)| value * Self::WEIGHT_DECAY.powi(i))
+            .sum()
+    }
+
+    /// Bonus pp that is awarded for the amount of scores.
+    pub fn bonus_pp(n_scores: usize) -> f64 {
+        let n_scores = n_scores.min(Self::BONUS_MAX_SCORES) as i32;
+
+        (417.0 - 1.0 / 3.0) * (1.0 - 0.995_f64.powi(n_scores))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(pp_jump_aim: f64, pp_speed: f64) -> OsuPerformanceAttributes {
+        OsuPerformanceAttributes {
+            pp: pp_jump_aim + pp_speed,
+            pp_jump_aim,
+            pp_speed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(OsuProfile::new(&[]), OsuProfile::default());
+    }
+
+    #[test]
+    fn skills_sorted_independently() {
+        let scores = [score(100.0, 10.0), score(50.0, 200.0)];
+        let profile = OsuProfile::new(&scores);
+
+        assert!((profile.pp_jump_aim - (100.0 + 50.0 * 0.95)).abs() < 1e-9);
+        assert!((profile.pp_speed - (200.0 + 10.0 * 0.95)).abs() < 1e-9);
+        assert!(profile.pp_acc.abs() < f64::EPSILON);
+        assert_eq!(profile.n_scores, 2);
+
+        let weighted = 250.0 + 110.0 * 0.95;
+        assert!((profile.pp - profile.bonus_pp - weighted).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bonus() {
+        assert!(OsuProfile::bonus_pp(0).abs() < f64::EPSILON);
+        assert!(OsuProfile::bonus_pp(1) > 0.0);
+        assert!(OsuProfile::bonus_pp(500) < OsuProfile::bonus_pp(1000));
+
+        let capped = OsuProfile::bonus_pp(OsuProfile::BONUS_MAX_SCORES);
+        assert!((OsuProfile::bonus_pp(5000) - capped).abs() < f64::EPSILON);
+        assert!(capped < 417.0);
+    }
+}
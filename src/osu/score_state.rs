@@ -38,6 +38,17 @@ pub struct OsuScoreState {
     pub misses: u32,
 }
 
+snapshot_state!(OsuScoreState {
+    max_combo,
+    large_tick_hits,
+    small_tick_hits,
+    slider_end_hits,
+    n300,
+    n100,
+    n50,
+    misses,
+});
+
 impl OsuScoreState {
     /// Create a new empty score state.
     pub const fn new() -> Self {
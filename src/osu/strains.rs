@@ -2,7 +2,10 @@ use rosu_map::section::general::GameMode;
 
 use crate::{any::difficulty::skills::StrainSkill, model::mode::ConvertError, Beatmap, Difficulty};
 
-use super::difficulty::{skills::OsuSkills, DifficultyValues};
+use super::difficulty::{
+    skills::{aim::Aim, OsuSkills},
+    DifficultyValues,
+};
 
 /// The result of calculating the strains on a osu! map.
 ///
@@ -17,10 +20,18 @@ pub struct OsuStrains {
     pub jump_aim: Vec<f64>,
     /// Strain peaks of the flow aim skill.
     pub flow_aim: Vec<f64>,
+    /// Strain peaks of the precision skill.
+    pub precision: Vec<f64>,
+    /// Strain peaks of the low approach rate reading skill.
+    pub reading: Vec<f64>,
     /// Strain peaks of the speed skill.
     pub speed: Vec<f64>,
     /// Strain peaks of the stamina skill.
     pub stamina: Vec<f64>,
+    /// Strain peaks of the accuracy skill.
+    pub accuracy: Vec<f64>,
+    /// Strain peaks of the flashlight skill.
+    pub flashlight: Vec<f64>,
 }
 
 impl OsuStrains {
@@ -34,13 +45,20 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<OsuStrains, Con
     let DifficultyValues {
         skills:
             OsuSkills {
-                aim,
-                raw_aim,
-                jump_aim,
-                flow_aim,
+                aim:
+                    Aim {
+                        all: aim,
+                        raw: raw_aim,
+                        jump: jump_aim,
+                        flow: flow_aim,
+                        precision,
+                        reading,
+                        ..
+                    },
                 speed,
                 stamina,
-                rhythm_complexity: _,
+                rhythm_complexity,
+                flashlight,
             },
         attrs: _,
     } = DifficultyValues::calculate(difficulty, &map);
@@ -50,7 +68,11 @@ pub fn strains(difficulty: &Difficulty, map: &Beatmap) -> Result<OsuStrains, Con
         raw_aim: raw_aim.into_current_strain_peaks().into_vec(),
         jump_aim: jump_aim.into_current_strain_peaks().into_vec(),
         flow_aim: flow_aim.into_current_strain_peaks().into_vec(),
+        precision: precision.into_current_strain_peaks().into_vec(),
+        reading: reading.into_current_strain_peaks().into_vec(),
         speed: speed.into_current_strain_peaks().into_vec(),
         stamina: stamina.into_current_strain_peaks().into_vec(),
+        accuracy: rhythm_complexity.into_current_strain_peaks().into_vec(),
+        flashlight: flashlight.into_current_strain_peaks().into_vec(),
     })
 }
@@ -0,0 +1,146 @@
+use super::OsuDifficultyAttributes;
+
+/// The dominant style of an osu!standard map.
+///
+/// Derived from [`OsuDifficultyAttributes`] through
+/// [`OsuDifficultyAttributes::style`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum OsuMapStyle {
+    /// Mostly spaced jumps.
+    Jump,
+    /// Mostly streams and flow aim.
+    Stream,
+    /// Aim that largely stems from small and precise patterns.
+    Tech,
+    /// Tapping dominates over aim, e.g. long and dense streams.
+    Alt,
+    /// No single style dominates.
+    Mixed,
+}
+
+impl OsuMapStyle {
+    /// Minimum share of stream objects for a map to be considered alt.
+    const ALT_STREAM_SHARE: f64 = 0.4;
+    /// Ratio by which tapping must exceed aim for a map to be considered alt.
+    const ALT_TAP_RATIO: f64 = 1.15;
+    /// Minimum share of the map's objects for jumps or streams to dominate.
+    const DOMINANT_SHARE: f64 = 0.5;
+    /// Minimum ratio of precision to aim for a map to be considered tech.
+    const TECH_PRECISION_RATIO: f64 = 0.5;
+
+    /// Classify a map's style based on its attributes.
+    pub fn classify(attrs: &OsuDifficultyAttributes) -> Self {
+        let aim = attrs.jump.max(attrs.flow);
+        let tap = attrs.speed.max(attrs.stamina);
+
+        if aim <= 0.0 && tap <= 0.0 {
+            return Self::Mixed;
+        }
+
+        if attrs.stream_share >= Self::ALT_STREAM_SHARE && tap > aim * Self::ALT_TAP_RATIO {
+            Self::Alt
+        } else if attrs.jump_share >= Self::DOMINANT_SHARE && attrs.jump >= attrs.flow {
+            Self::Jump
+        } else if attrs.stream_share >= Self::DOMINANT_SHARE && attrs.flow >= attrs.jump {
+            Self::Stream
+        } else if aim > 0.0 && attrs.precision >= aim * Self::TECH_PRECISION_RATIO {
+            Self::Tech
+        } else {
+            Self::Mixed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{osu::Osu, Beatmap, Difficulty};
+
+    use super::*;
+
+    fn attrs() -> OsuDifficultyAttributes {
+        OsuDifficultyAttributes {
+            jump: 2.0,
+            flow: 2.0,
+            precision: 0.2,
+            speed: 2.0,
+            stamina: 1.5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let attrs = OsuDifficultyAttributes::default();
+
+        assert_eq!(attrs.style(), OsuMapStyle::Mixed);
+    }
+
+    #[test]
+    fn jump() {
+        let attrs = OsuDifficultyAttributes {
+            jump: 3.0,
+            jump_share: 0.7,
+            stream_share: 0.1,
+            ..attrs()
+        };
+
+        assert_eq!(attrs.style(), OsuMapStyle::Jump);
+    }
+
+    #[test]
+    fn stream() {
+        let attrs = OsuDifficultyAttributes {
+            flow: 3.0,
+            stream_share: 0.6,
+            ..attrs()
+        };
+
+        assert_eq!(attrs.style(), OsuMapStyle::Stream);
+    }
+
+    #[test]
+    fn alt() {
+        let attrs = OsuDifficultyAttributes {
+            speed: 3.0,
+            stream_share: 0.6,
+            ..attrs()
+        };
+
+        assert_eq!(attrs.style(), OsuMapStyle::Alt);
+    }
+
+    #[test]
+    fn tech() {
+        let attrs = OsuDifficultyAttributes {
+            precision: 1.5,
+            jump_share: 0.3,
+            stream_share: 0.3,
+            ..attrs()
+        };
+
+        assert_eq!(attrs.style(), OsuMapStyle::Tech);
+    }
+
+    #[test]
+    fn mixed() {
+        let attrs = OsuDifficultyAttributes {
+            jump_share: 0.3,
+            stream_share: 0.3,
+            ..attrs()
+        };
+
+        assert_eq!(attrs.style(), OsuMapStyle::Mixed);
+    }
+
+    #[test]
+    fn map() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let attrs = Difficulty::new().calculate_for_mode::<Osu>(&map).unwrap();
+
+        assert!(attrs.flow_ratio > 0.0 && attrs.flow_ratio < 1.0);
+        assert!(attrs.mean_jump_dist > 0.0);
+        assert!(attrs.jump_share + attrs.stream_share <= 1.0);
+        assert_eq!(attrs.style(), OsuMapStyle::Jump);
+    }
+}
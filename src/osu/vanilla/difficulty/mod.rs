@@ -0,0 +1,268 @@
+use std::{cmp, pin::Pin};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::difficulty::{skills::StrainSkill, Difficulty},
+    model::{mode::ConvertError, mods::GameMods},
+    osu::{
+        attributes::OsuDifficultyAttributes,
+        convert::convert_objects,
+        difficulty::{scaling_factor::ScalingFactor, skills::strain::OsuStrainSkill},
+        object::OsuObject,
+    },
+    Beatmap,
+};
+
+use self::{
+    object::OsuDifficultyObject,
+    skills::{aim::Aim, flashlight::Flashlight, speed::Speed, OsuSkills},
+};
+
+use super::{attributes::OsuVanillaDifficultyAttributes, performance::PERFORMANCE_BASE_MULTIPLIER};
+
+pub mod object;
+pub mod skills;
+
+const DIFFICULTY_MULTIPLIER: f64 = 0.0675;
+
+pub fn difficulty(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+) -> Result<OsuVanillaDifficultyAttributes, ConvertError> {
+    let map = map.convert_ref(GameMode::Osu, difficulty.get_mods())?;
+
+    let DifficultyValues { skills, mut attrs } = DifficultyValues::calculate(difficulty, &map);
+
+    DifficultyValues::eval(&mut attrs, difficulty.get_mods(), &skills);
+
+    Ok(attrs)
+}
+
+pub struct DifficultyValues {
+    pub skills: OsuSkills,
+    pub attrs: OsuVanillaDifficultyAttributes,
+}
+
+impl DifficultyValues {
+    pub fn calculate(difficulty: &Difficulty, map: &Beatmap) -> Self {
+        let mods = difficulty.get_mods();
+        let take = difficulty.get_passed_objects();
+        let clock_rate = difficulty.get_clock_rate();
+
+        let map_attrs = map.attributes().difficulty(difficulty).build();
+        let scaling_factor = ScalingFactor::with_normalized_radius(
+            map_attrs.cs,
+            OsuDifficultyObject::NORMALIZED_RADIUS as f32,
+        );
+        let time_preempt = f64::from((map_attrs.hit_windows.ar * clock_rate) as f32);
+
+        // Object counts are gathered the same way for both scoring models
+        let mut counts = OsuDifficultyAttributes::default();
+
+        let mut osu_objects = convert_objects(
+            map,
+            &scaling_factor,
+            mods.reflection(),
+            time_preempt,
+            take,
+            &mut counts,
+        );
+
+        let attrs = OsuVanillaDifficultyAttributes {
+            ar: map_attrs.ar,
+            hp: map_attrs.hp,
+            great_hit_window: map_attrs.hit_windows.od_great,
+            ok_hit_window: map_attrs.hit_windows.od_ok.unwrap_or(0.0),
+            meh_hit_window: map_attrs.hit_windows.od_meh.unwrap_or(0.0),
+            n_circles: counts.n_circles,
+            n_sliders: counts.n_sliders,
+            n_large_ticks: counts.n_large_ticks,
+            n_spinners: counts.n_spinners,
+            max_combo: counts.max_combo,
+            ..Default::default()
+        };
+
+        let osu_object_iter = osu_objects.iter_mut().map(Pin::new);
+
+        let diff_objects =
+            Self::create_difficulty_objects(clock_rate, take, &scaling_factor, osu_object_iter);
+
+        let mut skills = OsuSkills::new(mods, &scaling_factor, &map_attrs, time_preempt);
+
+        // The first hit object has no difficulty object
+        let take_diff_objects = cmp::min(map.hit_objects.len(), take).saturating_sub(1);
+
+        for hit_object in diff_objects.iter().take(take_diff_objects) {
+            skills.process(hit_object, &diff_objects);
+        }
+
+        Self { skills, attrs }
+    }
+
+    /// Process the difficulty values and store the results in `attrs`.
+    pub fn eval(attrs: &mut OsuVanillaDifficultyAttributes, mods: &GameMods, skills: &OsuSkills) {
+        let OsuSkills {
+            aim,
+            aim_no_sliders,
+            speed,
+            flashlight,
+        } = skills;
+
+        let aim_difficulty_value = aim.cloned_difficulty_value();
+        let aim_no_sliders_difficulty_value = aim_no_sliders.cloned_difficulty_value();
+        let speed_difficulty_value = speed.cloned_difficulty_value();
+        let flashlight_difficulty_value = flashlight.cloned_difficulty_value();
+
+        let aim_difficult_slider_count = aim.get_difficult_sliders();
+        let speed_relevant_note_count = speed.relevant_note_count();
+        let aim_difficult_strain_count = aim.count_top_weighted_strains(aim_difficulty_value);
+        let speed_difficult_strain_count = speed.count_top_weighted_strains(speed_difficulty_value);
+
+        let mut aim_rating = aim_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
+        let aim_rating_no_sliders = aim_no_sliders_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
+        let mut speed_rating = speed_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
+        let mut flashlight_rating = flashlight_difficulty_value.sqrt() * DIFFICULTY_MULTIPLIER;
+
+        let slider_factor = if aim_rating > 0.0 {
+            aim_rating_no_sliders / aim_rating
+        } else {
+            1.0
+        };
+
+        if mods.td() {
+            aim_rating = aim_rating.powf(0.8);
+            flashlight_rating = flashlight_rating.powf(0.8);
+        }
+
+        if mods.rx() {
+            aim_rating *= 0.9;
+            speed_rating = 0.0;
+            flashlight_rating *= 0.7;
+        } else if mods.ap() {
+            speed_rating *= 0.5;
+            aim_rating = 0.0;
+            flashlight_rating *= 0.4;
+        }
+
+        let base_aim_performance = Aim::difficulty_to_performance(aim_rating);
+        let base_speed_performance = Speed::difficulty_to_performance(speed_rating);
+
+        let base_flashlight_performance = if mods.fl() {
+            Flashlight::difficulty_to_performance(flashlight_rating)
+        } else {
+            0.0
+        };
+
+        let base_performance = ((base_aim_performance).powf(1.1)
+            + (base_speed_performance).powf(1.1)
+            + (base_flashlight_performance).powf(1.1))
+        .powf(1.0 / 1.1);
+
+        let star_rating = if base_performance > 0.00001 {
+            PERFORMANCE_BASE_MULTIPLIER.cbrt()
+                * 0.027
+                * ((100_000.0 / 2.0_f64.powf(1.0 / 1.1) * base_performance).cbrt() + 4.0)
+        } else {
+            0.0
+        };
+
+        attrs.aim = aim_rating;
+        attrs.aim_difficult_slider_count = aim_difficult_slider_count;
+        attrs.speed = speed_rating;
+        attrs.flashlight = flashlight_rating;
+        attrs.slider_factor = slider_factor;
+        attrs.aim_difficult_strain_count = aim_difficult_strain_count;
+        attrs.speed_difficult_strain_count = speed_difficult_strain_count;
+        attrs.speed_note_count = speed_relevant_note_count;
+        attrs.stars = star_rating;
+    }
+
+    pub fn create_difficulty_objects<'a>(
+        clock_rate: f64,
+        take: usize,
+        scaling_factor: &ScalingFactor,
+        osu_objects: impl ExactSizeIterator<Item = Pin<&'a mut OsuObject>>,
+    ) -> Vec<OsuDifficultyObject<'a>> {
+        let mut osu_objects_iter = osu_objects
+            .map(|h| OsuDifficultyObject::compute_slider_cursor_pos(h, scaling_factor.radius))
+            .map(Pin::into_ref);
+
+        let Some(mut last) = osu_objects_iter.next().filter(|_| take > 0) else {
+            return Vec::new();
+        };
+
+        let mut last_last = None;
+
+        osu_objects_iter
+            .enumerate()
+            .map(|(idx, h)| {
+                let diff_object = OsuDifficultyObject::new(
+                    h.get_ref(),
+                    last.get_ref(),
+                    last_last.map(Pin::get_ref),
+                    clock_rate,
+                    idx,
+                    scaling_factor,
+                );
+
+                last_last = Some(last);
+                last = h;
+
+                diff_object
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calculate(mods: u32) -> OsuVanillaDifficultyAttributes {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        difficulty(&Difficulty::new().mods(mods), &map).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn nomod() {
+        let attrs = calculate(0);
+
+        assert_close(attrs.aim, 2.8768763397837276);
+        assert_close(attrs.aim_difficult_slider_count, 159.94435184493983);
+        assert_close(attrs.speed, 2.4859791945784644);
+        assert_close(attrs.flashlight, 2.287810214401711);
+        assert_close(attrs.slider_factor, 0.9804419804851772);
+        assert_close(attrs.speed_note_count, 203.4377249350029);
+        assert_close(attrs.aim_difficult_strain_count, 109.81975624515795);
+        assert_close(attrs.speed_difficult_strain_count, 79.45944001555702);
+        assert_close(attrs.stars, 5.653394946111255);
+        assert_eq!(attrs.n_objects(), 601);
+        assert_eq!(attrs.max_combo, 909);
+    }
+
+    #[test]
+    fn mods() {
+        let hd = calculate(8);
+        assert_close(hd.flashlight, 2.605769566257193);
+
+        let hr = calculate(16);
+        assert_close(hr.aim, 3.2524329530597154);
+        assert_close(hr.speed, 2.642601593996546);
+        assert_close(hr.speed_note_count, 178.23533084488034);
+        assert_close(hr.stars, 6.252509796432301);
+
+        let dt = calculate(64);
+        assert_close(dt.aim, 4.048049265438377);
+        assert_close(dt.speed, 3.5966087398365265);
+        assert_close(dt.stars, 8.041658803681496);
+
+        let fl = calculate(1024);
+        assert_close(fl.stars, 6.864894125872836);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{
+    any::difficulty::{
+        object::{HasStartTime, IDifficultyObject},
+        skills::strain_decay,
+    },
+    osu::{
+        object::{OsuObjectKind, OsuSlider},
+        vanilla::difficulty::object::OsuDifficultyObject,
+    },
+    util::strains_vec::StrainsVec,
+};
+
+define_skill! {
+    #[derive(Clone)]
+    pub struct Flashlight: StrainSkill => [OsuDifficultyObject<'a>][OsuDifficultyObject<'a>] {
+        current_strain: f64 = 0.0,
+        has_hidden_mod: bool,
+        scaling_factor: f64,
+        time_preempt: f64,
+        time_fade_in: f64,
+    }
+}
+
+impl Flashlight {
+    const SKILL_MULTIPLIER: f64 = 0.05512;
+    const STRAIN_DECAY_BASE: f64 = 0.15;
+
+    fn calculate_initial_strain(
+        &mut self,
+        time: f64,
+        curr: &OsuDifficultyObject<'_>,
+        objects: &[OsuDifficultyObject<'_>],
+    ) -> f64 {
+        let prev_start_time = curr
+            .previous(0, objects)
+            .map_or(0.0, HasStartTime::start_time);
+
+        self.current_strain * strain_decay(time - prev_start_time, Self::STRAIN_DECAY_BASE)
+    }
+
+    fn strain_value_at(
+        &mut self,
+        curr: &OsuDifficultyObject<'_>,
+        objects: &[OsuDifficultyObject<'_>],
+    ) -> f64 {
+        self.current_strain *= strain_decay(curr.delta_time, Self::STRAIN_DECAY_BASE);
+        self.current_strain += FlashlightEvaluator::evaluate_diff_of(
+            curr,
+            objects,
+            self.has_hidden_mod,
+            self.scaling_factor,
+            self.time_preempt,
+            self.time_fade_in,
+        ) * Self::SKILL_MULTIPLIER;
+
+        self.current_strain
+    }
+
+    pub fn difficulty_to_performance(difficulty: f64) -> f64 {
+        25.0 * f64::powf(difficulty, 2.0)
+    }
+
+    // Native rather than trait function so that it has priority over
+    // `StrainSkill::difficulty_value`
+    #[allow(clippy::needless_pass_by_value)]
+    fn difficulty_value(current_strain_peaks: StrainsVec) -> f64 {
+        current_strain_peaks.sum()
+    }
+}
+
+struct FlashlightEvaluator;
+
+impl FlashlightEvaluator {
+    const MAX_OPACITY_BONUS: f64 = 0.4;
+    const HIDDEN_BONUS: f64 = 0.2;
+
+    const MIN_VELOCITY: f64 = 0.5;
+    const SLIDER_MULTIPLIER: f64 = 1.3;
+
+    const MIN_ANGLE_MULTIPLIER: f64 = 0.2;
+
+    fn evaluate_diff_of<'a>(
+        curr: &'a OsuDifficultyObject<'a>,
+        diff_objects: &'a [OsuDifficultyObject<'a>],
+        hidden: bool,
+        scaling_factor: f64,
+        time_preempt: f64,
+        time_fade_in: f64,
+    ) -> f64 {
+        if curr.base.is_spinner() {
+            return 0.0;
+        }
+
+        let osu_curr = curr;
+        let osu_hit_obj = curr.base;
+
+        let mut small_dist_nerf = 1.0;
+        let mut cumulative_strain_time = 0.0;
+
+        let mut result = 0.0;
+
+        let mut last_obj = osu_curr;
+
+        let mut angle_repeat_count = 0.0;
+
+        // * This is iterating backwards in time from the current object.
+        for i in 0..osu_curr.idx.min(10) {
+            let Some(curr_obj) = curr.previous(i, diff_objects) else {
+                break;
+            };
+
+            let curr_hit_obj = curr_obj.base;
+
+            cumulative_strain_time += last_obj.strain_time;
+
+            if !curr_hit_obj.is_spinner() {
+                let jump_dist = f64::from(
+                    (osu_hit_obj.stacked_pos() - curr_hit_obj.stacked_end_pos()).length(),
+                );
+
+                // * We want to nerf objects that can be easily seen within the Flashlight circle radius.
+                if i == 0 {
+                    small_dist_nerf = (jump_dist / 75.0).min(1.0);
+                }
+
+                // * We also want to nerf stacks so that only the first object of the stack is accounted for.
+                let stack_nerf = ((curr_obj.lazy_jump_dist / scaling_factor) / 25.0).min(1.0);
+
+                // * Bonus based on how visible the object is.
+                let opacity_bonus = 1.0
+                    + Self::MAX_OPACITY_BONUS
+                        * (1.0
+                            - osu_curr.opacity_at(
+                                curr_hit_obj.start_time,
+                                hidden,
+                                time_preempt,
+                                time_fade_in,
+                            ));
+
+                result += stack_nerf * opacity_bonus * scaling_factor * jump_dist
+                    / cumulative_strain_time;
+
+                if let Some((curr_obj_angle, osu_curr_angle)) = curr_obj.angle.zip(osu_curr.angle) {
+                    // * Objects further back in time should count less for the nerf.
+                    if (curr_obj_angle - osu_curr_angle).abs() < 0.02 {
+                        angle_repeat_count += (1.0 - 0.1 * i as f64).max(0.0);
+                    }
+                }
+            }
+
+            last_obj = curr_obj;
+        }
+
+        result = (small_dist_nerf * result).powf(2.0);
+
+        // * Additional bonus for Hidden due to there being no approach circles.
+        if hidden {
+            result *= 1.0 + Self::HIDDEN_BONUS;
+        }
+
+        // * Nerf patterns with repeated angles.
+        result *= Self::MIN_ANGLE_MULTIPLIER
+            + (1.0 - Self::MIN_ANGLE_MULTIPLIER) / (angle_repeat_count + 1.0);
+
+        let mut slider_bonus = 0.0;
+
+        if let OsuObjectKind::Slider(ref slider) = osu_curr.base.kind {
+            slider_bonus = Self::slider_bonus(slider, scaling_factor, osu_curr.travel_time);
+        }
+
+        result += slider_bonus * Self::SLIDER_MULTIPLIER;
+
+        result
+    }
+
+    fn slider_bonus(slider: &OsuSlider, scaling_factor: f64, travel_time: f64) -> f64 {
+        // * Consider sliders that are travelling at least as fast as `min_velocity`.
+        let pixel_travel_dist = f64::from(slider.lazy_travel_dist) / scaling_factor;
+
+        let mut slider_bonus = (pixel_travel_dist / travel_time - Self::MIN_VELOCITY)
+            .max(0.0)
+            .powf(0.5);
+
+        // * Longer sliders require more memorisation.
+        slider_bonus *= pixel_travel_dist;
+
+        // * Nerf sliders with repeats, as less memorisation is required.
+        let repeat_count = slider.repeat_count();
+
+        if repeat_count > 0 {
+            slider_bonus /= (repeat_count + 1) as f64;
+        }
+
+        slider_bonus
+    }
+}
@@ -0,0 +1,5 @@
+pub use self::attributes::{OsuVanillaDifficultyAttributes, OsuVanillaPerformanceAttributes};
+
+pub mod attributes;
+pub mod difficulty;
+pub mod performance;
@@ -28,6 +28,19 @@ pub struct TaikoDifficultyAttributes {
     pub is_convert: bool,
 }
 
+snapshot_state!(TaikoDifficultyAttributes {
+    stamina,
+    rhythm,
+    color,
+    reading,
+    great_hit_window,
+    ok_hit_window,
+    mono_stamina_factor,
+    stars,
+    max_combo,
+    is_convert,
+});
+
 impl TaikoDifficultyAttributes {
     /// Return the maximum combo.
     pub const fn max_combo(&self) -> u32 {
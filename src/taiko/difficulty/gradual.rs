@@ -4,9 +4,14 @@ use rosu_map::section::general::GameMode;
 
 use crate::{
     any::difficulty::skills::StrainSkill,
-    model::{beatmap::HitWindows, hit_object::HitObject, mode::ConvertError},
+    model::{
+        beatmap::HitWindows,
+        hit_object::HitObject,
+        mode::ConvertError,
+        snapshot::{self, SnapshotError, SnapshotReader, SnapshotState, SnapshotWriter},
+    },
     taiko::convert,
-    util::sync::RefCount,
+    util::{object_times::ObjectTimes, sync::RefCount},
     Beatmap, Difficulty,
 };
 
@@ -56,7 +61,10 @@ pub struct TaikoGradualDifficulty {
     diff_objects_iter: Iter<'static, RefCount<TaikoDifficultyObject>>,
     skills: TaikoSkills,
     total_hits: usize,
+    times: ObjectTimes,
     first_combos: FirstTwoCombos,
+    checksum: u64,
+    final_attrs: Option<TaikoDifficultyAttributes>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -70,6 +78,7 @@ enum FirstTwoCombos {
 impl TaikoGradualDifficulty {
     /// Create a new difficulty attributes iterator for osu!taiko maps.
     pub fn new(difficulty: Difficulty, map: &Beatmap) -> Result<Self, ConvertError> {
+        let checksum = snapshot::checksum(map, &difficulty);
         let mut map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
 
         if let Some(seed) = difficulty.get_mods().random_seed() {
@@ -119,6 +128,14 @@ impl TaikoGradualDifficulty {
 
         let total_hits = map.hit_objects.iter().filter(|h| h.is_circle()).count();
 
+        let times = ObjectTimes::new(
+            map.hit_objects
+                .iter()
+                .filter(|h| h.is_circle())
+                .map(|h| h.start_time),
+            clock_rate,
+        );
+
         let diff_objects_iter = extend_lifetime(diff_objects.iter());
 
         Ok(Self {
@@ -129,9 +146,141 @@ impl TaikoGradualDifficulty {
             skills,
             attrs,
             total_hits,
+            times,
             first_combos,
+            checksum,
+            final_attrs: None,
         })
     }
+
+    /// Index of the first hit that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// The time is adjusted by the clock rate, e.g. with DT a hit at
+    /// 3000ms has started after 2000ms of playback.
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.times.passed_count(time)
+    }
+
+    /// Process all remaining hits that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed hits.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        let n = self.idx_at_time(time).saturating_sub(self.idx);
+
+        if n > 0 {
+            self.nth(n - 1);
+        }
+
+        n
+    }
+
+    /// Process all remaining hits that have started at the given playback
+    /// time in milliseconds and return the resulting attributes.
+    ///
+    /// Returns `None` if no hit has started yet.
+    pub fn attributes_at_time(&mut self, time: f64) -> Option<TaikoDifficultyAttributes> {
+        match self.idx_at_time(time).saturating_sub(self.idx) {
+            0 => self.current(),
+            n => self.nth(n - 1),
+        }
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// The snapshot contains the amount of processed hits, the skills'
+    /// strains, and the attributes so far.
+    ///
+    /// Use [`TaikoGradualDifficulty::from_snapshot`] to restore it.
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        snapshot::to_snapshot(self, GameMode::Taiko, self.checksum)
+    }
+
+    /// Restore a snapshot of [`TaikoGradualDifficulty::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let mut gradual = Self::new(difficulty, map)?;
+        let checksum = gradual.checksum;
+        snapshot::from_snapshot(&mut gradual, GameMode::Taiko, checksum, snapshot)?;
+
+        Ok(gradual)
+    }
+
+    /// The attributes after processing all hits.
+    ///
+    /// The processing state remains unchanged and the result is cached.
+    pub(crate) fn final_attributes(&mut self) -> Option<TaikoDifficultyAttributes> {
+        if self.final_attrs.is_none() {
+            let idx = self.idx;
+            let attrs = self.attrs.clone();
+            let skills = self.skills.clone();
+            let diff_objects_iter = self.diff_objects_iter.clone();
+
+            self.final_attrs = match self.len() {
+                0 => self.current(),
+                _ => self.nth(usize::MAX),
+            };
+
+            self.idx = idx;
+            self.attrs = attrs;
+            self.skills = skills;
+            self.diff_objects_iter = diff_objects_iter;
+        }
+
+        self.final_attrs.clone()
+    }
+
+    /// The attributes of all hits that have been processed so far.
+    pub(crate) fn current(&self) -> Option<TaikoDifficultyAttributes> {
+        if self.idx == 0 {
+            return None;
+        }
+
+        let mut attrs = self.attrs.clone();
+        let is_relax = self.difficulty.get_mods().rx();
+
+        DifficultyValues::eval(&mut attrs, self.skills.clone(), is_relax);
+
+        Some(attrs)
+    }
+}
+
+impl SnapshotState for TaikoGradualDifficulty {
+    fn write(&self, writer: &mut SnapshotWriter) {
+        let processed = self.diff_objects.objects.len() - self.diff_objects_iter.len();
+
+        self.idx.write(writer);
+        processed.write(writer);
+        self.attrs.write(writer);
+        self.skills.write(writer);
+    }
+
+    fn read(&mut self, reader: &mut SnapshotReader<'_>) -> Result<(), SnapshotError> {
+        let mut processed = 0_usize;
+        self.idx.read(reader)?;
+        processed.read(reader)?;
+
+        if self.idx > self.total_hits || processed > self.diff_objects.objects.len() {
+            return Err(SnapshotError::InvalidState);
+        }
+
+        self.diff_objects_iter = extend_lifetime(self.diff_objects.iter());
+
+        if processed > 0 {
+            self.diff_objects_iter.nth(processed - 1);
+        }
+
+        self.attrs.read(reader)?;
+        self.skills.read(reader)
+    }
 }
 
 fn extend_lifetime(
@@ -182,12 +331,7 @@ impl Iterator for TaikoGradualDifficulty {
 
         self.idx += 1;
 
-        let mut attrs = self.attrs.clone();
-        let is_relax = self.difficulty.get_mods().rx();
-
-        DifficultyValues::eval(&mut attrs, self.skills.clone(), is_relax);
-
-        Some(attrs)
+        self.current()
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -322,4 +466,54 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn time() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = TaikoGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+
+        assert_eq!(gradual.idx_at_time(-1.0), 0);
+        assert!(gradual.attributes_at_time(-1.0).is_none());
+
+        for time in [20_000.0, 20_000.0, 50_000.0, f64::MAX] {
+            let idx = gradual.idx_at_time(time);
+            let attrs = gradual.attributes_at_time(time).unwrap();
+
+            let expected = difficulty
+                .clone()
+                .passed_objects(idx as u32)
+                .calculate_for_mode::<Taiko>(&map)
+                .unwrap();
+
+            assert_eq!(gradual.idx, idx);
+            assert_eq!(attrs, expected);
+        }
+
+        assert_eq!(gradual.len(), 0);
+        assert_eq!(gradual.advance_to_time(f64::MAX), 0);
+    }
+
+    #[test]
+    fn snapshot() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(64); // DT
+        let mut gradual = TaikoGradualDifficulty::new(difficulty.clone(), &map).unwrap();
+        gradual.nth(299);
+
+        let snapshot = gradual.to_snapshot();
+        let restored =
+            TaikoGradualDifficulty::from_snapshot(difficulty.clone(), &map, &snapshot).unwrap();
+
+        assert_eq!(restored.idx, gradual.idx);
+        assert_eq!(restored.to_snapshot(), snapshot);
+        assert_eq!(restored.last(), gradual.last());
+
+        assert!(matches!(
+            TaikoGradualDifficulty::from_snapshot(Difficulty::new(), &map, &snapshot),
+            Err(SnapshotError::ChecksumMismatch)
+        ));
+    }
 }
@@ -16,6 +16,14 @@ pub struct TaikoSkills {
     pub single_color_stamina: Stamina,
 }
 
+snapshot_state!(TaikoSkills {
+    rhythm,
+    reading,
+    color,
+    stamina,
+    single_color_stamina,
+});
+
 impl TaikoSkills {
     pub fn new(great_hit_window: f64, is_convert: bool) -> Self {
         Self {
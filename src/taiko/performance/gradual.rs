@@ -1,6 +1,9 @@
 use crate::{
-    model::mode::ConvertError,
-    taiko::{difficulty::gradual::TaikoGradualDifficulty, TaikoScoreState},
+    any::{performance::projection, Projection},
+    model::{mode::ConvertError, snapshot::SnapshotError},
+    taiko::{
+        difficulty::gradual::TaikoGradualDifficulty, TaikoDifficultyAttributes, TaikoScoreState,
+    },
     Beatmap, Difficulty,
 };
 
@@ -106,19 +109,41 @@ impl TaikoGradualPerformance {
     ///
     /// Note that the count is zero-indexed, so `n=0` will process 1 object,
     /// `n=1` will process 2, and so on.
-    #[allow(clippy::missing_panics_doc, reason = "technically false positive")]
     pub fn nth(&mut self, state: TaikoScoreState, n: usize) -> Option<TaikoPerformanceAttributes> {
-        let performance = self
-            .difficulty
-            .nth(n)?
-            .performance()
-            .state(state)
-            .difficulty(self.difficulty.difficulty.clone())
-            .passed_objects(self.difficulty.idx as u32)
-            .calculate()
-            .expect("no conversion required");
+        let attrs = self.difficulty.nth(n)?;
 
-        Some(performance)
+        Some(self.calculate(attrs, state, self.difficulty.idx))
+    }
+
+    /// Index of the first hit that has not started yet at the given
+    /// playback time in milliseconds.
+    ///
+    /// See [`TaikoGradualDifficulty::idx_at_time`].
+    pub fn idx_at_time(&self, time: f64) -> usize {
+        self.difficulty.idx_at_time(time)
+    }
+
+    /// Process all remaining hits that have started at the given playback
+    /// time in milliseconds.
+    ///
+    /// Returns the amount of newly processed hits.
+    pub fn advance_to_time(&mut self, time: f64) -> usize {
+        self.difficulty.advance_to_time(time)
+    }
+
+    /// Process all remaining hits that have started at the given playback
+    /// time in milliseconds and calculate the performance attributes for the
+    /// resulting score state.
+    ///
+    /// Returns `None` if no hit has started yet.
+    pub fn attributes_at_time(
+        &mut self,
+        state: TaikoScoreState,
+        time: f64,
+    ) -> Option<TaikoPerformanceAttributes> {
+        let attrs = self.difficulty.attributes_at_time(time)?;
+
+        Some(self.calculate(attrs, state, self.difficulty.idx))
     }
 
     /// Returns the amount of remaining objects.
@@ -126,6 +151,86 @@ impl TaikoGradualPerformance {
     pub fn len(&self) -> usize {
         self.difficulty.len()
     }
+
+    /// Project the final score when hitting all remaining hits without
+    /// breaking combo while keeping the current ratio of 300s and 100s.
+    ///
+    /// The `state` must contain the hitresults of all hits that were
+    /// processed so far. The processing state remains unchanged.
+    ///
+    /// Score states don't carry the current combo so after a combo break the
+    /// current combo is assumed to be `0`. The projected max combo is then
+    /// the larger of the state's max combo and the combo of all remaining
+    /// hits. Combo that was rebuilt since the last break is not accounted
+    /// for so the projected max combo may be too low in that case.
+    ///
+    /// Returns `None` if the map has no hits.
+    pub fn projection(
+        &mut self,
+        state: TaikoScoreState,
+    ) -> Option<Projection<TaikoScoreState, TaikoPerformanceAttributes>> {
+        let final_attrs = self.difficulty.final_attributes()?;
+        let curr_max_combo = self.difficulty.current().map_or(0, |attrs| attrs.max_combo);
+
+        let remaining = final_attrs.max_combo - curr_max_combo;
+        let [n300, n100] = projection::distribute(remaining, [state.n300, state.n100]);
+
+        let projected = TaikoScoreState {
+            max_combo: projection::full_combo(
+                state.max_combo,
+                curr_max_combo,
+                final_attrs.max_combo,
+            ),
+            n300: state.n300 + n300,
+            n100: state.n100 + n100,
+            misses: state.misses,
+        };
+
+        let n_objects = self.difficulty.idx + self.difficulty.len();
+        let attributes = self.calculate(final_attrs, projected, n_objects);
+
+        Some(Projection {
+            state: projected,
+            attributes,
+        })
+    }
+
+    /// Serialize the processing state into a compact snapshot.
+    ///
+    /// See [`TaikoGradualDifficulty::to_snapshot`].
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        self.difficulty.to_snapshot()
+    }
+
+    /// Restore a snapshot of [`TaikoGradualPerformance::to_snapshot`].
+    ///
+    /// The [`Difficulty`] and [`Beatmap`] must be the same as for the
+    /// calculator that took the snapshot, otherwise
+    /// [`SnapshotError::ChecksumMismatch`] is returned.
+    pub fn from_snapshot(
+        difficulty: Difficulty,
+        map: &Beatmap,
+        snapshot: &[u8],
+    ) -> Result<Self, SnapshotError> {
+        let difficulty = TaikoGradualDifficulty::from_snapshot(difficulty, map, snapshot)?;
+
+        Ok(Self { difficulty })
+    }
+
+    fn calculate(
+        &self,
+        attrs: TaikoDifficultyAttributes,
+        state: TaikoScoreState,
+        passed_objects: usize,
+    ) -> TaikoPerformanceAttributes {
+        attrs
+            .performance()
+            .state(state)
+            .difficulty(self.difficulty.difficulty.clone())
+            .passed_objects(passed_objects as u32)
+            .calculate()
+            .expect("no conversion required")
+    }
 }
 
 #[cfg(test)]
@@ -183,4 +288,32 @@ mod tests {
             assert_eq!(next_gradual, expected);
         }
     }
+
+    #[test]
+    fn projection() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+
+        let difficulty = Difficulty::new().mods(88); // HDHRDT
+        let mut gradual = TaikoGradualPerformance::new(difficulty.clone(), &map).unwrap();
+        let mut gradual_2nd = TaikoGradualPerformance::new(difficulty.clone(), &map).unwrap();
+
+        let state = TaikoPerformance::new(&map)
+            .difficulty(difficulty.clone())
+            .passed_objects(100)
+            .generate_state()
+            .unwrap();
+
+        gradual.nth(state.clone(), 99).unwrap();
+        let projection = gradual.projection(state.clone()).unwrap();
+
+        let mut regular_calc = TaikoPerformance::new(&map).difficulty(difficulty);
+        assert_eq!(projection.state, regular_calc.generate_state().unwrap());
+        assert_eq!(projection.attributes, regular_calc.calculate().unwrap());
+
+        // The processing state is unaffected
+        assert_eq!(
+            gradual.next(state.clone()),
+            gradual_2nd.nth(state.clone(), 100)
+        );
+    }
 }
@@ -190,6 +190,8 @@ macro_rules! define_skill {
             }
         }
 
+        snapshot_state!($name { $( $field_name, )* });
+
         const _: () = {
             #[allow(unused_imports)]
             use crate::{
@@ -248,6 +250,10 @@ macro_rules! define_skill {
                 )
             }
 
+            fn object_strains(&self) -> &[f64] {
+                &self.strain_skill_object_strains
+            }
+
             fn save_current_peak(&mut self) {
                 self.strain_skill_strain_peaks.push(self.strain_skill_current_section_peak);
             }
@@ -334,3 +340,25 @@ macro_rules! define_skill {
         }
     };
 }
+
+/// Implement `SnapshotState` by reading and writing the given fields in order.
+macro_rules! snapshot_state {
+    ( $name:ident { $( $field_name:ident ),* $(,)? } ) => {
+        impl crate::model::snapshot::SnapshotState for $name {
+            #[allow(unused_variables)]
+            fn write(&self, writer: &mut crate::model::snapshot::SnapshotWriter) {
+                $( crate::model::snapshot::SnapshotState::write(&self.$field_name, writer); )*
+            }
+
+            #[allow(unused_variables)]
+            fn read(
+                &mut self,
+                reader: &mut crate::model::snapshot::SnapshotReader<'_>,
+            ) -> Result<(), crate::model::snapshot::SnapshotError> {
+                $( crate::model::snapshot::SnapshotState::read(&mut self.$field_name, reader)?; )*
+
+                Ok(())
+            }
+        }
+    };
+}
@@ -4,6 +4,7 @@ pub mod hint;
 pub mod interval_grouping;
 pub mod limited_queue;
 pub mod map_or_attrs;
+pub mod object_times;
 pub mod random;
 pub mod sort;
 pub mod special_functions;
@@ -0,0 +1,40 @@
+/// Start times of the objects that a gradual calculator iterates over.
+///
+/// Times are adjusted by the clock rate so that they correspond to the
+/// elapsed playback time.
+#[derive(Clone, Debug, Default)]
+pub struct ObjectTimes {
+    times: Box<[f64]>,
+}
+
+impl ObjectTimes {
+    pub fn new(start_times: impl Iterator<Item = f64>, clock_rate: f64) -> Self {
+        Self {
+            times: start_times.map(|time| time / clock_rate).collect(),
+        }
+    }
+
+    /// Amount of objects whose start time is at or before `time`.
+    ///
+    /// This is also the index of the first object that has not started yet.
+    pub fn passed_count(&self, time: f64) -> usize {
+        self.times.partition_point(|&start_time| start_time <= time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passed_count() {
+        let times = ObjectTimes::new([300.0, 600.0, 600.0, 900.0].into_iter(), 1.5);
+
+        assert_eq!(times.passed_count(-1.0), 0);
+        assert_eq!(times.passed_count(199.0), 0);
+        assert_eq!(times.passed_count(200.0), 1);
+        assert_eq!(times.passed_count(400.0), 3);
+        assert_eq!(times.passed_count(10_000.0), 4);
+        assert_eq!(ObjectTimes::default().passed_count(100.0), 0);
+    }
+}